@@ -1,5 +1,40 @@
 pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
 pub const BONDING_CURVE_SEED: &[u8] = b"bonding_curve";
 pub const CURVE_TOKEN_ACCOUNT_SEED: &[u8] = b"curve_token_account";
+pub const LAUNCH_PURCHASE_SEED: &[u8] = b"launch_purchase";
+pub const USER_POSITION_SEED: &[u8] = b"user_position";
+pub const CREATOR_FEE_VAULT_SEED: &[u8] = b"creator_fee_vault";
+pub const REFERRER_SEED: &[u8] = b"referrer";
+pub const PROTOCOL_FEE_VAULT_SEED: &[u8] = b"protocol_fee_vault";
+pub const FEE_SPLIT_SEED: &[u8] = b"fee_split";
+pub const PRICE_CANDLES_SEED: &[u8] = b"price_candles";
+pub const LIMIT_ORDER_SEED: &[u8] = b"limit_order";
+pub const LIMIT_ORDER_ESCROW_SEED: &[u8] = b"limit_order_escrow";
+pub const DCA_ORDER_SEED: &[u8] = b"dca_order";
+pub const INTENT_AUTHORITY_SEED: &[u8] = b"intent_authority";
+pub const INTENT_NONCE_SEED: &[u8] = b"intent_nonce";
+pub const CURVE_BY_INDEX_SEED: &[u8] = b"curve_by_index";
+pub const CURVE_BY_CREATOR_SEED: &[u8] = b"curve_by_creator";
+pub const CREATOR_CURVE_COUNTER_SEED: &[u8] = b"creator_curve_counter";
+pub const SELL_INTENT_DOMAIN: &[u8] = b"hook-amm:sell_intent:v1"; // prefix of signed intent messages
 
-pub const FEE_BASIS_POINTS: u16 = 100; // 1% fee
\ No newline at end of file
+pub const FEE_BASIS_POINTS: u16 = 100; // 1% fee
+pub const FEE_DENOMINATOR: u64 = 10_000;
+pub const DEFAULT_MAX_CREATOR_FEE_BPS: u16 = 100; // 1% creator fee cap
+pub const MAX_CREATOR_FEE_BPS_LIMIT: u16 = 500; // Admin cannot allow more than 5%
+pub const DEFAULT_REFERRAL_SHARE_BPS: u16 = 2_000; // 20% of the protocol fee
+pub const MAX_FEE_SPLIT_RECIPIENTS: usize = 8;
+pub const MAX_FEE_TIERS: usize = 8;
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000; // Tiers cannot charge more than 10%
+pub const MAX_SELL_TAX_BPS: u16 = 2_500; // Launch sell tax cannot start above 25%
+pub const MAX_DYNAMIC_FEE_BPS: u16 = 500; // Volatility surcharge cannot exceed 5%
+
+pub const TWAP_OBSERVATION_COUNT: usize = 16;
+pub const TWAP_OBSERVATION_INTERVAL_SECONDS: i64 = 300; // 16 observations cover at least 80 minutes
+
+pub const CANDLE_MINUTE_SECONDS: i64 = 60;
+pub const CANDLE_MINUTE_COUNT: usize = 60; // last hour of minute candles
+pub const CANDLE_HOUR_SECONDS: i64 = 3_600;
+pub const CANDLE_HOUR_COUNT: usize = 48; // last two days of hour candles
+
+pub const PRICE_PRECISION: u128 = 1_000_000_000; // Prices are lamports per token unit scaled by 1e9
@@ -24,4 +24,66 @@ pub enum ErrorCode {
     VirtualReservesTooSmall,
     #[msg("Insufficient balance")]
     InsufficientBalance,
+    #[msg("Reserve overflow")]
+    ReserveOverflow,
+    #[msg("Reserve underflow")]
+    ReserveUnderflow,
+    #[msg("Fee overflow")]
+    FeeOverflow,
+    #[msg("Curve is insolvent")]
+    CurveInsolvent,
+    #[msg("Invalid launch parameters")]
+    InvalidLaunchParams,
+    #[msg("Trading has not started")]
+    TradingNotStarted,
+    #[msg("Launch purchase account required during the protected window")]
+    LaunchPurchaseRequired,
+    #[msg("Wallet buy cap exceeded")]
+    WalletBuyCapExceeded,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Invalid fee")]
+    InvalidFee,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("Cannot refer yourself")]
+    SelfReferral,
+    #[msg("Invalid fee split")]
+    InvalidFeeSplit,
+    #[msg("Fee split recipient accounts do not match")]
+    FeeSplitRecipientMismatch,
+    #[msg("Invalid fee tier")]
+    InvalidFeeTier,
+    #[msg("Price impact too high")]
+    PriceImpactTooHigh,
+    #[msg("Not enough price history for the requested TWAP window")]
+    TwapWindowUnavailable,
+    #[msg("Invalid swap route")]
+    InvalidSwapRoute,
+    #[msg("Order has expired")]
+    OrderExpired,
+    #[msg("Order price has not been reached")]
+    OrderNotTriggered,
+    #[msg("Missing order token account")]
+    OrderTokenAccountRequired,
+    #[msg("DCA fill is not due yet")]
+    DcaFillNotDue,
+    #[msg("Token account does not belong to the recipient")]
+    InvalidRecipient,
+    #[msg("Missing or invalid ed25519 intent signature")]
+    InvalidIntentSignature,
+    #[msg("Intent deadline has passed")]
+    IntentExpired,
+    #[msg("Intent nonce does not match")]
+    InvalidIntentNonce,
+    #[msg("Intent proceeds would leave the user below the rent-exempt minimum")]
+    IntentProceedsBelowRent,
+    #[msg("Account already uses the current layout")]
+    AlreadyMigrated,
+    #[msg("Fee recipient account required when no fee split is enabled")]
+    FeeRecipientRequired,
+    #[msg("Price candles account required for this curve")]
+    PriceCandlesRequired,
+    #[msg("Token account can only be closed by its owner once the sell empties it")]
+    TokenAccountNotClosable,
 }
\ No newline at end of file
@@ -4,9 +4,116 @@ use anchor_lang::prelude::*;
 pub struct TradeEvent {
     pub mint: Pubkey,
     pub user: Pubkey,
+    /// Owner of the tokens bought, or receiver of the SOL from a sell
+    pub recipient: Pubkey,
     pub sol_amount: u64,
     pub token_amount: u64,
     pub is_buy: bool,
+    /// Part of a buy's `sol_amount` input left with the buyer after a partial fill
+    pub refunded_sol_amount: u64,
+    /// Portion of the fee accrued to the curve creator
+    pub creator_fee: u64,
+    /// Portion of the protocol fee accrued to the referrer
+    pub referral_fee: u64,
     pub virtual_sol_reserves: u64,
     pub virtual_token_reserves: u64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct CurveVerificationEvent {
+    pub mint: Pubkey,
+    pub expected_lamports: u64,
+    pub actual_lamports: u64,
+    pub expected_token_balance: u64,
+    pub actual_token_balance: u64,
+    pub is_solvent: bool,
+    pub is_consistent: bool,
+}
+
+#[event]
+pub struct CreatorFeesClaimedEvent {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreatorTransferredEvent {
+    pub mint: Pubkey,
+    pub previous_creator: Pubkey,
+    pub new_creator: Pubkey,
+}
+
+#[event]
+pub struct ReferralFeesClaimedEvent {
+    pub referrer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ProtocolFeesWithdrawnEvent {
+    pub fee_recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LimitOrderPlacedEvent {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub is_buy: bool,
+    pub amount_in: u64,
+    pub target_price: u128,
+    pub expiry_timestamp: i64,
+}
+
+#[event]
+pub struct LimitOrderExecutedEvent {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub keeper: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub keeper_reward: u64,
+}
+
+#[event]
+pub struct LimitOrderCancelledEvent {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct DcaOrderCreatedEvent {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount_per_fill: u64,
+    pub fill_count: u64,
+    pub interval_seconds: i64,
+}
+
+#[event]
+pub struct DcaFillEvent {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub keeper: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub fills_remaining: u64,
+}
+
+#[event]
+pub struct DcaOrderCancelledEvent {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub fills_remaining: u64,
+}
+
+#[event]
+pub struct IntentExecutedEvent {
+    pub user: Pubkey,
+    pub relayer: Pubkey,
+    pub nonce: u64,
+    pub relayer_fee: u64,
+}
@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{GlobalConfig, BondingCurve};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken};
+use crate::state::{GlobalConfig, BondingCurve, CreatorFeeVault, ProtocolFeeVault, LaunchPurchase, Referrer, UserPosition, PriceCandles};
 use crate::constants::*;
 use crate::errors::ErrorCode;
-use crate::events::TradeEvent;
-use crate::utils::calculate_buy_amount;
+use crate::quote::{buy_fee_rates, quote_buy, BuyQuote};
+use crate::utils::{settle_buy, BuyFunding, TradeSettlement};
 
 #[derive(Accounts)]
 pub struct Buy<'info> {
@@ -23,17 +23,24 @@ pub struct Buy<'info> {
     )]
     pub curve_token_account: InterfaceAccount<'info, TokenAccount>,
     
+    /// CHECK: Associated token account of `recipient`, or of `user` when no recipient is given; created if missing
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = user,
-        associated_token::token_program = token_program,
+        address = get_associated_token_address_with_program_id(
+            &recipient.as_ref().map_or(user.key(), |recipient| recipient.key()),
+            &mint.key(),
+            &token_program.key(),
+        ) @ ErrorCode::InvalidRecipient,
     )]
-    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub user_token_account: UncheckedAccount<'info>,
     
+    /// Pays for the purchase
     #[account(mut)]
     pub user: Signer<'info>,
     
+    /// CHECK: Owner of the purchased tokens; defaults to `user`
+    pub recipient: Option<UncheckedAccount<'info>>,
+    
     pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
@@ -42,12 +49,63 @@ pub struct Buy<'info> {
     )]
     pub global_config: Account<'info, GlobalConfig>,
     
-    /// CHECK: Fee recipient from global config
     #[account(
         mut,
-        constraint = fee_recipient.key() == global_config.fee_recipient
+        seeds = [PROTOCOL_FEE_VAULT_SEED],
+        bump
+    )]
+    pub protocol_fee_vault: Account<'info, ProtocolFeeVault>,
+    
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_VAULT_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+    
+    /// Launch allocation of the token owner; required only while the curve's launch window is active
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + LaunchPurchase::INIT_SPACE,
+        seeds = [
+            LAUNCH_PURCHASE_SEED,
+            bonding_curve.key().as_ref(),
+            recipient.as_ref().map_or(user.key(), |recipient| recipient.key()).as_ref()
+        ],
+        bump
+    )]
+    pub launch_purchase: Option<Account<'info, LaunchPurchase>>,
+    
+    /// Optional frontend referrer credited with a share of the protocol fee
+    #[account(
+        mut,
+        seeds = [REFERRER_SEED, referrer.authority.as_ref()],
+        bump
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+    
+    /// Optional trading history on this curve for the token owner (`recipient`, or `user` when no recipient is given)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [
+            USER_POSITION_SEED,
+            bonding_curve.key().as_ref(),
+            recipient.as_ref().map_or(user.key(), |recipient| recipient.key()).as_ref()
+        ],
+        bump
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>,
+    
+    /// Optional OHLCV candles for this curve, required once created with `initialize_price_candles`
+    #[account(
+        mut,
+        seeds = [PRICE_CANDLES_SEED, bonding_curve.key().as_ref()],
+        bump
     )]
-    pub fee_recipient: AccountInfo<'info>,
+    pub price_candles: Option<AccountLoader<'info, PriceCandles>>,
     
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -59,69 +117,109 @@ pub fn buy_handler<'info>(
     ctx: Context<'_, '_, '_, 'info, Buy<'info>>, 
     sol_amount: u64, 
     min_token_amount: u64
-) -> Result<()> {
+) -> Result<BuyQuote> {
     require!(sol_amount > 0, ErrorCode::InvalidAmount);
     require!(!ctx.accounts.bonding_curve.complete, ErrorCode::CurveComplete);
     
-    // Calculate output amount using constant product formula
-    let fee_amount = sol_amount
-        .checked_mul(FEE_BASIS_POINTS as u64)
-        .unwrap()
-        .checked_div(10000)
-        .unwrap();
-    let sol_amount_after_fee = sol_amount.checked_sub(fee_amount).unwrap();
-    
-    let token_amount = calculate_buy_amount(
-        sol_amount_after_fee,
-        ctx.accounts.bonding_curve.virtual_sol_reserves + ctx.accounts.bonding_curve.real_sol_reserves,
-        ctx.accounts.bonding_curve.virtual_token_reserves + ctx.accounts.bonding_curve.token_total_supply - ctx.accounts.bonding_curve.real_token_reserves,
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(
+        now >= ctx.accounts.bonding_curve.trading_start_timestamp,
+        ErrorCode::TradingNotStarted
+    );
+    
+    let referral_share_bps = match ctx.accounts.referrer.as_ref() {
+        Some(referrer) => {
+            require!(referrer.authority != ctx.accounts.user.key(), ErrorCode::SelfReferral);
+            ctx.accounts.global_config.referral_share_bps
+        }
+        None => 0,
+    };
+    let fee_rates = buy_fee_rates(&ctx.accounts.bonding_curve, &ctx.accounts.global_config, now, referral_share_bps)?;
+    
+    // Calculate output amount using constant product formula, capped at what the curve still holds
+    let quote = quote_buy(
+        &ctx.accounts.bonding_curve,
+        &fee_rates,
+        sol_amount,
+        ctx.accounts.curve_token_account.amount,
     )?;
+    let token_amount = quote.token_amount;
     
     require!(token_amount >= min_token_amount, ErrorCode::SlippageExceeded);
     
-    // Update reserves - for buy: SOL increases, tokens decrease (but real_token_reserves tracks tokens taken OUT)
-    ctx.accounts.bonding_curve.real_sol_reserves = ctx.accounts.bonding_curve.real_sol_reserves
-        .checked_add(sol_amount_after_fee)
-        .unwrap();
-    ctx.accounts.bonding_curve.real_token_reserves = ctx.accounts.bonding_curve.real_token_reserves
-        .checked_add(token_amount)
-        .unwrap();
-    
-    // Transfer SOL from buyer to curve (only the amount after fee)
-    let sol_transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
-        &ctx.accounts.user.key(),
-        &ctx.accounts.bonding_curve.key(),
-        sol_amount_after_fee,
-    );
-    anchor_lang::solana_program::program::invoke(
-        &sol_transfer_ix,
-        &[
-            ctx.accounts.user.to_account_info(),
-            ctx.accounts.bonding_curve.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-    )?;
-    
-    // Transfer fee directly from user to fee recipient
-    if fee_amount > 0 {
-        let fee_transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.user.key(),
-            &ctx.accounts.global_config.fee_recipient,
-            fee_amount,
-        );
-        anchor_lang::solana_program::program::invoke(
-            &fee_transfer_ix,
-            &[
-                ctx.accounts.user.to_account_info(),
-                ctx.accounts.fee_recipient.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-        )?;
+    // Enforce the per-wallet cap on the token owner while the launch window is active
+    let token_owner = ctx.accounts.recipient.as_ref().map_or(ctx.accounts.user.key(), |recipient| recipient.key());
+    if ctx.accounts.bonding_curve.is_launch_window_active(now) {
+        let max_tokens_per_wallet = ctx.accounts.bonding_curve.max_tokens_per_wallet;
+        let bonding_curve_key = ctx.accounts.bonding_curve.key();
+        let launch_purchase = ctx.accounts.launch_purchase
+            .as_mut()
+            .ok_or(ErrorCode::LaunchPurchaseRequired)?;
+        
+        let tokens_purchased = launch_purchase.tokens_purchased
+            .checked_add(token_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        if tokens_purchased > max_tokens_per_wallet {
+            msg!(
+                "Wallet buy cap exceeded: tokens_purchased={} token_amount={} max_tokens_per_wallet={}",
+                launch_purchase.tokens_purchased,
+                token_amount,
+                max_tokens_per_wallet
+            );
+            return err!(ErrorCode::WalletBuyCapExceeded);
+        }
+        
+        launch_purchase.bonding_curve = bonding_curve_key;
+        launch_purchase.user = token_owner;
+        launch_purchase.tokens_purchased = tokens_purchased;
     }
     
+    // Create the buyer's token account on first purchase (works for both token programs)
+    let user_info = ctx.accounts.user.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    let token_owner_info = match ctx.accounts.recipient.as_ref() {
+        Some(recipient) => recipient.to_account_info(),
+        None => user_info.clone(),
+    };
+    associated_token::create_idempotent(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: user_info.clone(),
+            associated_token: ctx.accounts.user_token_account.to_account_info(),
+            authority: token_owner_info.clone(),
+            mint: ctx.accounts.mint.to_account_info(),
+            system_program: system_program_info.clone(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
+    
+    // Update reserves, move the SOL and fees, and record the trade
+    let mint_key = ctx.accounts.mint.key();
+    settle_buy(
+        TradeSettlement {
+            mint: mint_key,
+            global_config: &ctx.accounts.global_config,
+            bonding_curve: &mut ctx.accounts.bonding_curve,
+            protocol_fee_vault: &mut ctx.accounts.protocol_fee_vault,
+            creator_fee_vault: &mut ctx.accounts.creator_fee_vault,
+            referrer: ctx.accounts.referrer.as_mut(),
+            price_candles: ctx.accounts.price_candles.as_ref(),
+            user_position: ctx.accounts.user_position.as_mut(),
+            user: user_info.key(),
+            recipient: token_owner,
+            now,
+            slot: clock.slot,
+        },
+        &quote,
+        BuyFunding::Payer {
+            payer: &user_info,
+            system_program: &system_program_info,
+        },
+    )?;
+    
     // Transfer tokens from curve to buyer (handles Token-2022 with hooks)
     let bonding_curve_seed = BONDING_CURVE_SEED;
-    let mint_key = ctx.accounts.mint.key();
     let bump = ctx.bumps.bonding_curve;
     
     let signer_seeds = &[
@@ -131,25 +229,15 @@ pub fn buy_handler<'info>(
     ];
     
     crate::utils::perform_token_transfer(
-        &ctx.accounts.curve_token_account,
-        &ctx.accounts.user_token_account,
+        &ctx.accounts.curve_token_account.to_account_info(),
+        &ctx.accounts.user_token_account.to_account_info(),
         &ctx.accounts.bonding_curve.to_account_info(),
         &ctx.accounts.token_program,
         &ctx.accounts.mint,
-        token_amount,
+        quote.token_amount,
         &[signer_seeds],
         ctx.remaining_accounts,
     )?;
     
-    emit!(TradeEvent {
-        mint: ctx.accounts.mint.key(),
-        user: ctx.accounts.user.key(),
-        sol_amount,
-        token_amount,
-        is_buy: true,
-        virtual_sol_reserves: ctx.accounts.bonding_curve.virtual_sol_reserves + ctx.accounts.bonding_curve.real_sol_reserves,
-        virtual_token_reserves: ctx.accounts.bonding_curve.virtual_token_reserves + ctx.accounts.bonding_curve.token_total_supply - ctx.accounts.bonding_curve.real_token_reserves,
-    });
-    
-    Ok(())
+    Ok(quote)
 }
\ No newline at end of file
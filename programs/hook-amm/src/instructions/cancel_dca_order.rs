@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::DcaOrder;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::DcaOrderCancelledEvent;
+
+#[derive(Accounts)]
+pub struct CancelDcaOrder<'info> {
+    /// Closing returns the rent and the remaining escrow
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ ErrorCode::Unauthorized,
+        seeds = [
+            DCA_ORDER_SEED,
+            dca_order.bonding_curve.as_ref(),
+            owner.key().as_ref(),
+            dca_order.order_id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub dca_order: Account<'info, DcaOrder>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn cancel_dca_order_handler(ctx: Context<CancelDcaOrder>) -> Result<()> {
+    emit!(DcaOrderCancelledEvent {
+        order: ctx.accounts.dca_order.key(),
+        owner: ctx.accounts.owner.key(),
+        fills_remaining: ctx.accounts.dca_order.fills_remaining,
+    });
+
+    Ok(())
+}
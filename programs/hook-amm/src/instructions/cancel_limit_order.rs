@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+use crate::state::{BondingCurve, LimitOrder};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::LimitOrderCancelledEvent;
+
+#[derive(Accounts)]
+pub struct CancelLimitOrder<'info> {
+    /// Closing returns the rent, any escrowed SOL and the unpaid keeper reward
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ ErrorCode::Unauthorized,
+        has_one = bonding_curve,
+        seeds = [
+            LIMIT_ORDER_SEED,
+            bonding_curve.key().as_ref(),
+            owner.key().as_ref(),
+            limit_order.order_id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub limit_order: Account<'info, LimitOrder>,
+
+    #[account(
+        mut,
+        seeds = [LIMIT_ORDER_ESCROW_SEED, limit_order.key().as_ref()],
+        bump,
+    )]
+    pub order_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn cancel_limit_order_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelLimitOrder<'info>>,
+) -> Result<()> {
+    if !ctx.accounts.limit_order.is_buy {
+        let order_token_account = ctx.accounts.order_token_account
+            .as_ref()
+            .ok_or(ErrorCode::OrderTokenAccountRequired)?;
+        let owner_token_account = ctx.accounts.owner_token_account
+            .as_ref()
+            .ok_or(ErrorCode::OrderTokenAccountRequired)?;
+
+        let bonding_curve_key = ctx.accounts.bonding_curve.key();
+        let owner_key = ctx.accounts.owner.key();
+        let order_id_bytes = ctx.accounts.limit_order.order_id.to_le_bytes();
+        let signer_seeds: &[&[u8]] = &[
+            LIMIT_ORDER_SEED,
+            bonding_curve_key.as_ref(),
+            owner_key.as_ref(),
+            order_id_bytes.as_ref(),
+            &[ctx.bumps.limit_order],
+        ];
+
+        // Return the escrowed tokens and the escrow account's rent
+        crate::utils::perform_token_transfer(
+            &order_token_account.to_account_info(),
+            &owner_token_account.to_account_info(),
+            &ctx.accounts.limit_order.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            order_token_account.amount,
+            &[signer_seeds],
+            ctx.remaining_accounts,
+        )?;
+
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: order_token_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.limit_order.to_account_info(),
+            },
+            &[signer_seeds],
+        ))?;
+    }
+
+    emit!(LimitOrderCancelledEvent {
+        order: ctx.accounts.limit_order.key(),
+        owner: ctx.accounts.owner.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::{BondingCurve, CreatorFeeVault};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::CreatorFeesClaimedEvent;
+use crate::utils::transfer_lamports;
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        has_one = creator @ ErrorCode::Unauthorized
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_VAULT_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+pub fn claim_creator_fees_handler(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+    let amount = ctx.accounts.creator_fee_vault.claimable();
+    require!(amount > 0, ErrorCode::NothingToClaim);
+
+    transfer_lamports(
+        &ctx.accounts.creator_fee_vault.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        amount,
+    )?;
+
+    let creator_fee_vault = &mut ctx.accounts.creator_fee_vault;
+    creator_fee_vault.total_claimed = creator_fee_vault.total_claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    emit!(CreatorFeesClaimedEvent {
+        mint: ctx.accounts.mint.key(),
+        creator: ctx.accounts.creator.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::Referrer;
+use crate::constants::REFERRER_SEED;
+use crate::errors::ErrorCode;
+use crate::events::ReferralFeesClaimedEvent;
+use crate::utils::transfer_lamports;
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(
+        mut,
+        seeds = [REFERRER_SEED, authority.key().as_ref()],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn claim_referral_fees_handler(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    let amount = ctx.accounts.referrer.claimable();
+    require!(amount > 0, ErrorCode::NothingToClaim);
+
+    transfer_lamports(
+        &ctx.accounts.referrer.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        amount,
+    )?;
+
+    let referrer = &mut ctx.accounts.referrer;
+    referrer.total_claimed = referrer.total_claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    emit!(ReferralFeesClaimedEvent {
+        referrer: ctx.accounts.authority.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::UserPosition;
+use crate::constants::USER_POSITION_SEED;
+
+#[derive(Accounts)]
+pub struct CloseUserPosition<'info> {
+    #[account(
+        mut,
+        close = user,
+        seeds = [USER_POSITION_SEED, user_position.bonding_curve.as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn close_user_position_handler(_ctx: Context<CloseUserPosition>) -> Result<()> {
+    Ok(())
+}
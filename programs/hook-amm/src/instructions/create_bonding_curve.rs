@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, set_authority, SetAuthority};
 use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{GlobalConfig, BondingCurve};
+use crate::state::{GlobalConfig, BondingCurve, CreatorFeeVault, CreatorCurveCounter, CurveRegistryEntry};
 use crate::constants::*;
 use crate::errors::ErrorCode;
 
@@ -27,6 +27,15 @@ pub struct CreateBondingCurve<'info> {
     )]
     pub curve_token_account: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CreatorFeeVault::INIT_SPACE,
+        seeds = [CREATOR_FEE_VAULT_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
     #[account(
         mut,
         token::mint = mint,
@@ -52,6 +61,38 @@ pub struct CreateBondingCurve<'info> {
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CurveRegistryEntry::INIT_SPACE,
+        seeds = [CURVE_BY_INDEX_SEED, global_config.total_curves.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub curve_by_index: Box<Account<'info, CurveRegistryEntry>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + CreatorCurveCounter::INIT_SPACE,
+        seeds = [CREATOR_CURVE_COUNTER_SEED, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_curve_counter: Box<Account<'info, CreatorCurveCounter>>,
+
+    /// Keyed by the launching creator; not updated by `transfer_creator`
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CurveRegistryEntry::INIT_SPACE,
+        seeds = [
+            CURVE_BY_CREATOR_SEED,
+            creator.key().as_ref(),
+            creator_curve_counter.curve_count.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub curve_by_creator: Box<Account<'info, CurveRegistryEntry>>,
+
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -63,6 +104,30 @@ pub struct CreateBondingCurveParams {
     pub initial_supply: u64,
     pub virtual_token_reserves: u64,
     pub virtual_sol_reserves: u64,
+    pub launch_params: Option<LaunchParams>,
+    /// Must lie within the global config's creator fee bounds
+    pub creator_fee_bps: u16,
+    /// Index into the global config's fee tiers
+    pub fee_tier: u8,
+    pub sell_tax_schedule: Option<SellTaxSchedule>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct SellTaxSchedule {
+    /// Sell fee when trading starts; must be at least the tier's sell fee
+    pub start_fee_bps: u16,
+    /// Seconds after trading starts until the sell fee reaches the tier's sell fee
+    pub duration_seconds: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LaunchParams {
+    /// Unix timestamp before which buys are rejected; past values start trading immediately
+    pub trading_start_timestamp: i64,
+    /// Length of the protected window in seconds, starting at `trading_start_timestamp`
+    pub protected_window_seconds: i64,
+    /// Maximum tokens a single wallet may buy during the protected window
+    pub max_tokens_per_wallet: u64,
 }
 
 pub fn create_bonding_curve_handler<'info>(
@@ -86,6 +151,50 @@ pub fn create_bonding_curve_handler<'info>(
         ErrorCode::InsufficientBalance
     );
 
+    require!(
+        params.creator_fee_bps >= ctx.accounts.global_config.min_creator_fee_bps
+            && params.creator_fee_bps <= ctx.accounts.global_config.max_creator_fee_bps,
+        ErrorCode::InvalidFee
+    );
+
+    let fee_tier = *ctx.accounts.global_config.fee_tiers
+        .get(params.fee_tier as usize)
+        .ok_or(ErrorCode::InvalidFeeTier)?;
+
+    let (sell_tax_start_bps, sell_tax_duration_seconds) = match params.sell_tax_schedule {
+        Some(schedule) => {
+            require!(
+                schedule.start_fee_bps >= fee_tier.sell_fee_bps
+                    && schedule.start_fee_bps <= MAX_SELL_TAX_BPS
+                    && schedule.duration_seconds > 0,
+                ErrorCode::InvalidFee
+            );
+            (schedule.start_fee_bps, schedule.duration_seconds)
+        }
+        None => (fee_tier.sell_fee_bps, 0),
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let (trading_start_timestamp, protected_window_seconds, max_tokens_per_wallet) = match params.launch_params {
+        Some(launch) => {
+            require!(launch.protected_window_seconds >= 0, ErrorCode::InvalidLaunchParams);
+            require!(
+                launch.protected_window_seconds == 0 || launch.max_tokens_per_wallet > 0,
+                ErrorCode::InvalidLaunchParams
+            );
+            require!(
+                launch.max_tokens_per_wallet <= params.initial_supply,
+                ErrorCode::InvalidLaunchParams
+            );
+            (
+                launch.trading_start_timestamp.max(now),
+                launch.protected_window_seconds,
+                launch.max_tokens_per_wallet,
+            )
+        }
+        None => (now, 0, 0),
+    };
+
     let bonding_curve = &mut ctx.accounts.bonding_curve;
     let global_config = &mut ctx.accounts.global_config;
 
@@ -99,13 +208,45 @@ pub fn create_bonding_curve_handler<'info>(
     bonding_curve.token_total_supply = params.initial_supply;
     bonding_curve.complete = false;
     bonding_curve.index = global_config.total_curves;
+    bonding_curve.trading_start_timestamp = trading_start_timestamp;
+    bonding_curve.protected_window_seconds = protected_window_seconds;
+    bonding_curve.max_tokens_per_wallet = max_tokens_per_wallet;
+    bonding_curve.creator_fee_bps = params.creator_fee_bps;
+    bonding_curve.protocol_fees_accrued = 0;
+    bonding_curve.buy_fee_bps = fee_tier.buy_fee_bps;
+    bonding_curve.sell_fee_bps = fee_tier.sell_fee_bps;
+    bonding_curve.sell_tax_start_bps = sell_tax_start_bps;
+    bonding_curve.sell_tax_duration_seconds = sell_tax_duration_seconds;
+    bonding_curve.start_price_history(now);
+
+    let creator_fee_vault = &mut ctx.accounts.creator_fee_vault;
+    creator_fee_vault.bonding_curve = bonding_curve.key();
+    creator_fee_vault.total_accrued = 0;
+    creator_fee_vault.total_claimed = 0;
+
+    // Register the curve by global index and by creator so clients can enumerate without scanning
+    let registry_entry = CurveRegistryEntry {
+        bonding_curve: bonding_curve.key(),
+        mint: bonding_curve.mint,
+        index: bonding_curve.index,
+    };
+    ctx.accounts.curve_by_index.set_inner(registry_entry.clone());
+    ctx.accounts.curve_by_creator.set_inner(registry_entry);
+
+    let creator_curve_counter = &mut ctx.accounts.creator_curve_counter;
+    creator_curve_counter.creator = ctx.accounts.creator.key();
+    creator_curve_counter.curve_count = creator_curve_counter.curve_count
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
 
-    global_config.total_curves += 1;
+    global_config.total_curves = global_config.total_curves
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
 
     // Transfer tokens from creator to curve token account (handles Token-2022 with hooks)
     crate::utils::perform_token_transfer(
-        &ctx.accounts.creator_token_account,
-        &ctx.accounts.curve_token_account,
+        &ctx.accounts.creator_token_account.to_account_info(),
+        &ctx.accounts.curve_token_account.to_account_info(),
         &ctx.accounts.creator.to_account_info(),
         &ctx.accounts.token_program,
         &ctx.accounts.mint,
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::{BondingCurve, DcaOrder};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::DcaOrderCreatedEvent;
+use crate::utils::transfer_sol;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CreateDcaOrderParams {
+    /// Caller-chosen id, unique per owner and curve
+    pub order_id: u64,
+    pub amount_per_fill: u64,
+    pub fill_count: u64,
+    pub interval_seconds: i64,
+    pub max_price: u128,
+    pub keeper_reward: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: CreateDcaOrderParams)]
+pub struct CreateDcaOrder<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DcaOrder::INIT_SPACE,
+        seeds = [
+            DCA_ORDER_SEED,
+            bonding_curve.key().as_ref(),
+            owner.key().as_ref(),
+            params.order_id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub dca_order: Account<'info, DcaOrder>,
+
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_dca_order_handler(ctx: Context<CreateDcaOrder>, params: CreateDcaOrderParams) -> Result<()> {
+    require!(params.amount_per_fill > 0, ErrorCode::InvalidAmount);
+    require!(params.fill_count > 0, ErrorCode::InvalidAmount);
+    require!(params.interval_seconds > 0, ErrorCode::InvalidAmount);
+    require!(params.max_price > 0, ErrorCode::InvalidAmount);
+    require!(!ctx.accounts.bonding_curve.complete, ErrorCode::CurveComplete);
+
+    let escrow_amount = DcaOrder::escrow_amount(params.amount_per_fill, params.keeper_reward, params.fill_count)?;
+    transfer_sol(
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.dca_order.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        escrow_amount,
+    )?;
+
+    let dca_order = &mut ctx.accounts.dca_order;
+    dca_order.owner = ctx.accounts.owner.key();
+    dca_order.bonding_curve = ctx.accounts.bonding_curve.key();
+    dca_order.order_id = params.order_id;
+    dca_order.amount_per_fill = params.amount_per_fill;
+    dca_order.fills_remaining = params.fill_count;
+    dca_order.interval_seconds = params.interval_seconds;
+    dca_order.next_fill_timestamp = Clock::get()?.unix_timestamp;
+    dca_order.max_price = params.max_price;
+    dca_order.keeper_reward = params.keeper_reward;
+
+    emit!(DcaOrderCreatedEvent {
+        order: dca_order.key(),
+        owner: dca_order.owner,
+        mint: ctx.accounts.mint.key(),
+        amount_per_fill: params.amount_per_fill,
+        fill_count: params.fill_count,
+        interval_seconds: params.interval_seconds,
+    });
+
+    Ok(())
+}
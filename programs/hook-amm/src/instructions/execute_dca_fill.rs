@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{GlobalConfig, BondingCurve, CreatorFeeVault, ProtocolFeeVault, DcaOrder, PriceCandles};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::DcaFillEvent;
+use crate::quote::{buy_fee_rates, quote_buy};
+use crate::utils::{settle_buy, transfer_lamports, BuyFunding, TradeSettlement};
+
+#[derive(Accounts)]
+pub struct ExecuteDcaFill<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = bonding_curve,
+        seeds = [
+            DCA_ORDER_SEED,
+            bonding_curve.key().as_ref(),
+            owner.key().as_ref(),
+            dca_order.order_id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub dca_order: Box<Account<'info, DcaOrder>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Order owner from the DCA order
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [CURVE_TOKEN_ACCOUNT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_FEE_VAULT_SEED],
+        bump
+    )]
+    pub protocol_fee_vault: Box<Account<'info, ProtocolFeeVault>>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_VAULT_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Box<Account<'info, CreatorFeeVault>>,
+
+    /// Optional OHLCV candles for this curve, required once created with `initialize_price_candles`
+    #[account(
+        mut,
+        seeds = [PRICE_CANDLES_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub price_candles: Option<AccountLoader<'info, PriceCandles>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Executes one due fill of a DCA order and pays the keeper. The order closes to its owner after the last fill.
+pub fn execute_dca_fill_handler<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteDcaFill<'info>>) -> Result<()> {
+    require!(!ctx.accounts.bonding_curve.complete, ErrorCode::CurveComplete);
+    require!(ctx.accounts.dca_order.fills_remaining > 0, ErrorCode::InvalidAmount);
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(now >= ctx.accounts.dca_order.next_fill_timestamp, ErrorCode::DcaFillNotDue);
+    require!(
+        now >= ctx.accounts.bonding_curve.trading_start_timestamp,
+        ErrorCode::TradingNotStarted
+    );
+    ctx.accounts.bonding_curve.require_launch_window_closed(now)?;
+
+    let fee_rates = buy_fee_rates(&ctx.accounts.bonding_curve, &ctx.accounts.global_config, now, 0)?;
+    let quote = quote_buy(
+        &ctx.accounts.bonding_curve,
+        &fee_rates,
+        ctx.accounts.dca_order.amount_per_fill,
+        ctx.accounts.curve_token_account.amount,
+    )?;
+
+    // Slippage limit: the fill's average price must not exceed the order's max price
+    let max_price = ctx.accounts.dca_order.max_price;
+    let average_price = (quote.sol_amount_after_fee as u128)
+        .checked_mul(PRICE_PRECISION)
+        .ok_or(ErrorCode::Overflow)?
+        / (quote.token_amount.max(1) as u128);
+    if average_price > max_price {
+        msg!("DCA fill above max price: average_price={} max_price={}", average_price, max_price);
+        return err!(ErrorCode::SlippageExceeded);
+    }
+
+    let dca_order_info = ctx.accounts.dca_order.to_account_info();
+    let owner_key = ctx.accounts.owner.key();
+    settle_buy(
+        TradeSettlement {
+            mint: ctx.accounts.mint.key(),
+            global_config: &ctx.accounts.global_config,
+            bonding_curve: &mut ctx.accounts.bonding_curve,
+            protocol_fee_vault: &mut ctx.accounts.protocol_fee_vault,
+            creator_fee_vault: &mut ctx.accounts.creator_fee_vault,
+            referrer: None,
+            price_candles: ctx.accounts.price_candles.as_ref(),
+            user_position: None,
+            user: owner_key,
+            recipient: owner_key,
+            now,
+            slot: clock.slot,
+        },
+        &quote,
+        BuyFunding::Escrow(&dca_order_info),
+    )?;
+
+    let mint_key = ctx.accounts.mint.key();
+    let signer_seeds = &[
+        BONDING_CURVE_SEED,
+        mint_key.as_ref(),
+        &[ctx.bumps.bonding_curve],
+    ];
+    crate::utils::perform_token_transfer(
+        &ctx.accounts.curve_token_account.to_account_info(),
+        &ctx.accounts.owner_token_account.to_account_info(),
+        &ctx.accounts.bonding_curve.to_account_info(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        quote.token_amount,
+        &[signer_seeds],
+        ctx.remaining_accounts,
+    )?;
+
+    let keeper_reward = ctx.accounts.dca_order.keeper_reward;
+    transfer_lamports(&dca_order_info, &ctx.accounts.keeper.to_account_info(), keeper_reward)?;
+
+    let dca_order = &mut ctx.accounts.dca_order;
+    dca_order.fills_remaining -= 1;
+    dca_order.next_fill_timestamp = now
+        .checked_add(dca_order.interval_seconds)
+        .ok_or(ErrorCode::Overflow)?;
+    let fills_remaining = dca_order.fills_remaining;
+
+    emit!(DcaFillEvent {
+        order: dca_order.key(),
+        owner: dca_order.owner,
+        keeper: ctx.accounts.keeper.key(),
+        sol_amount: quote.sol_amount,
+        token_amount: quote.token_amount,
+        fills_remaining,
+    });
+
+    // Return the rent and any partial-fill refunds once the schedule is done
+    if fills_remaining == 0 {
+        ctx.accounts.dca_order.close(ctx.accounts.owner.to_account_info())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+use crate::state::{GlobalConfig, BondingCurve, CreatorFeeVault, ProtocolFeeVault, LimitOrder, PriceCandles};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::LimitOrderExecutedEvent;
+use crate::quote::{buy_fee_rates, quote_buy, quote_sell, sell_fee_rates};
+use crate::utils::{settle_buy, settle_sell, transfer_lamports, BuyFunding, TradeSettlement};
+
+#[derive(Accounts)]
+pub struct ExecuteOrder<'info> {
+    /// Closing returns the rent and any unspent SOL to the owner
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        has_one = bonding_curve,
+        seeds = [
+            LIMIT_ORDER_SEED,
+            bonding_curve.key().as_ref(),
+            owner.key().as_ref(),
+            limit_order.order_id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub limit_order: Box<Account<'info, LimitOrder>>,
+
+    /// Escrow of a sell order's tokens
+    #[account(
+        mut,
+        seeds = [LIMIT_ORDER_ESCROW_SEED, limit_order.key().as_ref()],
+        bump,
+    )]
+    pub order_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Receives a buy order's tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Order owner from the limit order
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [CURVE_TOKEN_ACCOUNT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_FEE_VAULT_SEED],
+        bump
+    )]
+    pub protocol_fee_vault: Box<Account<'info, ProtocolFeeVault>>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_VAULT_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Box<Account<'info, CreatorFeeVault>>,
+
+    /// Optional OHLCV candles for this curve, required once created with `initialize_price_candles`
+    #[account(
+        mut,
+        seeds = [PRICE_CANDLES_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub price_candles: Option<AccountLoader<'info, PriceCandles>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Fills a triggered limit order in full through the curve and pays the keeper.
+pub fn execute_order_handler<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteOrder<'info>>) -> Result<()> {
+    require!(!ctx.accounts.bonding_curve.complete, ErrorCode::CurveComplete);
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(now <= ctx.accounts.limit_order.expiry_timestamp, ErrorCode::OrderExpired);
+
+    let spot_price = ctx.accounts.bonding_curve.spot_price()?;
+    if !ctx.accounts.limit_order.is_triggered(spot_price) {
+        msg!(
+            "Order not triggered: spot_price={} target_price={} is_buy={}",
+            spot_price,
+            ctx.accounts.limit_order.target_price,
+            ctx.accounts.limit_order.is_buy
+        );
+        return err!(ErrorCode::OrderNotTriggered);
+    }
+
+    let limit_order_info = ctx.accounts.limit_order.to_account_info();
+    let amount_in = ctx.accounts.limit_order.amount_in;
+
+    let amount_out = if ctx.accounts.limit_order.is_buy {
+        require!(
+            now >= ctx.accounts.bonding_curve.trading_start_timestamp,
+            ErrorCode::TradingNotStarted
+        );
+        ctx.accounts.bonding_curve.require_launch_window_closed(now)?;
+        let owner_token_account = ctx.accounts.owner_token_account
+            .as_ref()
+            .ok_or(ErrorCode::OrderTokenAccountRequired)?;
+
+        let fee_rates = buy_fee_rates(&ctx.accounts.bonding_curve, &ctx.accounts.global_config, now, 0)?;
+        let quote = quote_buy(
+            &ctx.accounts.bonding_curve,
+            &fee_rates,
+            amount_in,
+            ctx.accounts.curve_token_account.amount,
+        )?;
+        require!(quote.token_amount >= ctx.accounts.limit_order.min_amount_out, ErrorCode::SlippageExceeded);
+
+        let owner_key = ctx.accounts.owner.key();
+        settle_buy(
+            TradeSettlement {
+                mint: ctx.accounts.mint.key(),
+                global_config: &ctx.accounts.global_config,
+                bonding_curve: &mut ctx.accounts.bonding_curve,
+                protocol_fee_vault: &mut ctx.accounts.protocol_fee_vault,
+                creator_fee_vault: &mut ctx.accounts.creator_fee_vault,
+                referrer: None,
+                price_candles: ctx.accounts.price_candles.as_ref(),
+                user_position: None,
+                user: owner_key,
+                recipient: owner_key,
+                now,
+                slot: clock.slot,
+            },
+            &quote,
+            BuyFunding::Escrow(&limit_order_info),
+        )?;
+
+        let mint_key = ctx.accounts.mint.key();
+        let signer_seeds = &[
+            BONDING_CURVE_SEED,
+            mint_key.as_ref(),
+            &[ctx.bumps.bonding_curve],
+        ];
+        crate::utils::perform_token_transfer(
+            &ctx.accounts.curve_token_account.to_account_info(),
+            &owner_token_account.to_account_info(),
+            &ctx.accounts.bonding_curve.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            quote.token_amount,
+            &[signer_seeds],
+            ctx.remaining_accounts,
+        )?;
+
+        quote.token_amount
+    } else {
+        let order_token_account = ctx.accounts.order_token_account
+            .as_ref()
+            .ok_or(ErrorCode::OrderTokenAccountRequired)?;
+
+        let fee_rates = sell_fee_rates(&ctx.accounts.bonding_curve, &ctx.accounts.global_config, now, 0)?;
+        let quote = quote_sell(&ctx.accounts.bonding_curve, &fee_rates, amount_in)?;
+        require!(
+            quote.sol_amount_after_fee >= ctx.accounts.limit_order.min_amount_out,
+            ErrorCode::SlippageExceeded
+        );
+
+        let bonding_curve_key = ctx.accounts.bonding_curve.key();
+        let owner_key = ctx.accounts.owner.key();
+        let order_id_bytes = ctx.accounts.limit_order.order_id.to_le_bytes();
+        let signer_seeds: &[&[u8]] = &[
+            LIMIT_ORDER_SEED,
+            bonding_curve_key.as_ref(),
+            owner_key.as_ref(),
+            order_id_bytes.as_ref(),
+            &[ctx.bumps.limit_order],
+        ];
+        crate::utils::perform_token_transfer(
+            &order_token_account.to_account_info(),
+            &ctx.accounts.curve_token_account.to_account_info(),
+            &limit_order_info,
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            amount_in,
+            &[signer_seeds],
+            ctx.remaining_accounts,
+        )?;
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: order_token_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: limit_order_info.clone(),
+            },
+            &[signer_seeds],
+        ))?;
+
+        transfer_lamports(
+            &ctx.accounts.bonding_curve.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            quote.sol_amount_after_fee,
+        )?;
+        settle_sell(
+            TradeSettlement {
+                mint: ctx.accounts.mint.key(),
+                global_config: &ctx.accounts.global_config,
+                bonding_curve: &mut ctx.accounts.bonding_curve,
+                protocol_fee_vault: &mut ctx.accounts.protocol_fee_vault,
+                creator_fee_vault: &mut ctx.accounts.creator_fee_vault,
+                referrer: None,
+                price_candles: ctx.accounts.price_candles.as_ref(),
+                user_position: None,
+                user: owner_key,
+                recipient: owner_key,
+                now,
+                slot: clock.slot,
+            },
+            &quote,
+            quote.sol_amount_after_fee,
+        )?;
+
+        quote.sol_amount_after_fee
+    };
+
+    // The keeper reward was escrowed on the order at placement
+    let keeper_reward = ctx.accounts.limit_order.keeper_reward;
+    transfer_lamports(&limit_order_info, &ctx.accounts.keeper.to_account_info(), keeper_reward)?;
+
+    emit!(LimitOrderExecutedEvent {
+        order: ctx.accounts.limit_order.key(),
+        owner: ctx.accounts.owner.key(),
+        keeper: ctx.accounts.keeper.key(),
+        amount_in,
+        amount_out,
+        keeper_reward,
+    });
+
+    Ok(())
+}
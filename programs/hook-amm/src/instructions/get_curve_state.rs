@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::quote::{buy_fee_rates, sell_fee_rates};
+use crate::instructions::Quote;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CurveState {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    /// Tokens still held by the curve and available to buy
+    pub remaining_tokens: u64,
+    /// Lamports per token unit, scaled by `PRICE_PRECISION`
+    pub spot_price: u128,
+    /// Protocol fee currently charged on each side, including any sell tax and dynamic fee
+    pub buy_fee_bps: u16,
+    pub sell_fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub trading_start_timestamp: i64,
+    pub complete: bool,
+}
+
+pub fn get_curve_state_handler(ctx: Context<Quote>) -> Result<CurveState> {
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    let now = Clock::get()?.unix_timestamp;
+
+    Ok(CurveState {
+        mint: bonding_curve.mint,
+        creator: bonding_curve.creator,
+        virtual_sol_reserves: bonding_curve.current_sol_reserves()?,
+        virtual_token_reserves: bonding_curve.current_token_reserves()?,
+        real_sol_reserves: bonding_curve.real_sol_reserves,
+        real_token_reserves: bonding_curve.real_token_reserves,
+        remaining_tokens: ctx.accounts.curve_token_account.amount,
+        spot_price: bonding_curve.spot_price()?,
+        buy_fee_bps: buy_fee_rates(bonding_curve, &ctx.accounts.global_config, now, 0)?.protocol_fee_bps,
+        sell_fee_bps: sell_fee_rates(bonding_curve, &ctx.accounts.global_config, now, 0)?.protocol_fee_bps,
+        creator_fee_bps: bonding_curve.creator_fee_bps,
+        trading_start_timestamp: bonding_curve.trading_start_timestamp,
+        complete: bonding_curve.complete,
+    })
+}
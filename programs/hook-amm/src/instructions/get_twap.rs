@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::BondingCurve;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+/// Prices are lamports per token unit, scaled by `PRICE_PRECISION`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TwapResult {
+    pub spot_price: u128,
+    pub twap_price: u128,
+    /// Start of the window actually used, at or before `now - window_seconds`
+    pub window_start_timestamp: i64,
+    pub timestamp: i64,
+    pub price_cumulative: u128,
+}
+
+pub fn get_twap_handler(ctx: Context<GetTwap>, window_seconds: i64) -> Result<TwapResult> {
+    require!(window_seconds > 0, ErrorCode::InvalidAmount);
+
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    let now = Clock::get()?.unix_timestamp;
+
+    let observation = bonding_curve
+        .twap_observation_before(now, window_seconds)
+        .ok_or_else(|| {
+            msg!("No observation older than {} seconds", window_seconds);
+            error!(ErrorCode::TwapWindowUnavailable)
+        })?;
+
+    let price_cumulative = bonding_curve.price_cumulative_at(now)?;
+    let elapsed = now.saturating_sub(observation.timestamp);
+    require!(elapsed > 0, ErrorCode::TwapWindowUnavailable);
+
+    let twap_price = price_cumulative.wrapping_sub(observation.price_cumulative) / elapsed as u128;
+
+    Ok(TwapResult {
+        spot_price: bonding_curve.spot_price()?,
+        twap_price,
+        window_start_timestamp: observation.timestamp,
+        timestamp: now,
+        price_cumulative,
+    })
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::GlobalConfig;
-use crate::constants::GLOBAL_CONFIG_SEED;
+use crate::state::{GlobalConfig, ProtocolFeeVault};
+use crate::constants::{GLOBAL_CONFIG_SEED, PROTOCOL_FEE_VAULT_SEED};
 
 #[derive(Accounts)]
 pub struct InitializeGlobalConfig<'info> {
@@ -13,6 +13,15 @@ pub struct InitializeGlobalConfig<'info> {
     )]
     pub global_config: Account<'info, GlobalConfig>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProtocolFeeVault::INIT_SPACE,
+        seeds = [PROTOCOL_FEE_VAULT_SEED],
+        bump
+    )]
+    pub protocol_fee_vault: Account<'info, ProtocolFeeVault>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -27,5 +36,10 @@ pub fn initialize_global_config_handler(ctx: Context<InitializeGlobalConfig>) ->
     global_config.authority = ctx.accounts.authority.key();
     global_config.fee_recipient = ctx.accounts.fee_recipient.key();
     global_config.total_curves = 0;
+    global_config.set_default_parameters();
+
+    let protocol_fee_vault = &mut ctx.accounts.protocol_fee_vault;
+    protocol_fee_vault.total_accrued = 0;
+    protocol_fee_vault.total_withdrawn = 0;
     Ok(())
 }
\ No newline at end of file
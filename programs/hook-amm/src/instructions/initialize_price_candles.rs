@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::{BondingCurve, PriceCandles};
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct InitializePriceCandles<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PriceCandles::SIZE,
+        seeds = [PRICE_CANDLES_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub price_candles: AccountLoader<'info, PriceCandles>,
+
+    #[account(mut)]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_price_candles_handler(ctx: Context<InitializePriceCandles>) -> Result<()> {
+    let mut price_candles = ctx.accounts.price_candles.load_init()?;
+    price_candles.bonding_curve = ctx.accounts.bonding_curve.key();
+    ctx.accounts.bonding_curve.price_candles_initialized = true;
+    Ok(())
+}
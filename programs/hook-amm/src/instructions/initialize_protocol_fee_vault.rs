@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, ProtocolFeeVault};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+/// Creates the protocol fee vault for a global config initialized before the
+/// vault existed, after `migrate_global_config` has brought it to the current
+/// layout. New deployments get the vault from `initialize_global_config`.
+#[derive(Accounts)]
+pub struct InitializeProtocolFeeVault<'info> {
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProtocolFeeVault::INIT_SPACE,
+        seeds = [PROTOCOL_FEE_VAULT_SEED],
+        bump
+    )]
+    pub protocol_fee_vault: Account<'info, ProtocolFeeVault>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_protocol_fee_vault_handler(ctx: Context<InitializeProtocolFeeVault>) -> Result<()> {
+    let protocol_fee_vault = &mut ctx.accounts.protocol_fee_vault;
+    protocol_fee_vault.total_accrued = 0;
+    protocol_fee_vault.total_withdrawn = 0;
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::{BondingCurve, CreatorFeeVault, GlobalConfig};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::utils::realloc_account;
+
+/// `BondingCurve` as written by program versions before launch, fee and price tracking fields were added.
+#[derive(AnchorDeserialize)]
+struct LegacyBondingCurve {
+    mint: Pubkey,
+    creator: Pubkey,
+    virtual_token_reserves: u64,
+    virtual_sol_reserves: u64,
+    real_token_reserves: u64,
+    real_sol_reserves: u64,
+    token_total_supply: u64,
+    complete: bool,
+    index: u64,
+}
+
+/// Grows a curve launched by an earlier program version to the current layout
+/// and creates its creator fee vault. The curve keeps its reserves, charges the
+/// first fee tier with no creator fee, and has no launch window or sell tax.
+/// Run after `migrate_global_config`.
+#[derive(Accounts)]
+pub struct MigrateBondingCurve<'info> {
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// CHECK: Old layout does not deserialize as `BondingCurve`; read by hand in the handler
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub bonding_curve: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CreatorFeeVault::INIT_SPACE,
+        seeds = [CREATOR_FEE_VAULT_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Pays the rent for the added space and the vault
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_bonding_curve_handler(ctx: Context<MigrateBondingCurve>) -> Result<()> {
+    let bonding_curve_info = ctx.accounts.bonding_curve.to_account_info();
+    let space = 8 + BondingCurve::INIT_SPACE;
+
+    let legacy = {
+        let data = bonding_curve_info.try_borrow_data()?;
+        require!(data.len() < space, ErrorCode::AlreadyMigrated);
+        require!(
+            data.starts_with(BondingCurve::DISCRIMINATOR),
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyBondingCurve::deserialize(&mut &data[8..])?
+    };
+
+    let fee_tier = *ctx.accounts.global_config.fee_tiers
+        .first()
+        .ok_or(ErrorCode::InvalidFeeTier)?;
+
+    // Only the rent for the added space is paid in, so real SOL reserves stay backed as before
+    realloc_account(
+        &bonding_curve_info,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        space,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut bonding_curve = BondingCurve {
+        mint: legacy.mint,
+        creator: legacy.creator,
+        virtual_token_reserves: legacy.virtual_token_reserves,
+        virtual_sol_reserves: legacy.virtual_sol_reserves,
+        real_token_reserves: legacy.real_token_reserves,
+        real_sol_reserves: legacy.real_sol_reserves,
+        token_total_supply: legacy.token_total_supply,
+        complete: legacy.complete,
+        index: legacy.index,
+        trading_start_timestamp: now,
+        buy_fee_bps: fee_tier.buy_fee_bps,
+        sell_fee_bps: fee_tier.sell_fee_bps,
+        sell_tax_start_bps: fee_tier.sell_fee_bps,
+        ..Default::default()
+    };
+    bonding_curve.start_price_history(now);
+    bonding_curve.try_serialize(&mut &mut bonding_curve_info.try_borrow_mut_data()?[..])?;
+
+    let creator_fee_vault = &mut ctx.accounts.creator_fee_vault;
+    creator_fee_vault.bonding_curve = bonding_curve_info.key();
+    creator_fee_vault.total_accrued = 0;
+    creator_fee_vault.total_claimed = 0;
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::GlobalConfig;
+use crate::constants::GLOBAL_CONFIG_SEED;
+use crate::errors::ErrorCode;
+use crate::utils::realloc_account;
+
+/// `GlobalConfig` as written by program versions before fees, tiers and risk limits were added.
+#[derive(AnchorDeserialize)]
+struct LegacyGlobalConfig {
+    authority: Pubkey,
+    fee_recipient: Pubkey,
+    total_curves: u64,
+}
+
+/// Grows a global config created by an earlier program version to the current
+/// layout and fills the new fields with the `initialize_global_config` defaults.
+/// Existing deployments run this before `initialize_protocol_fee_vault`.
+#[derive(Accounts)]
+pub struct MigrateGlobalConfig<'info> {
+    /// CHECK: Old layout does not deserialize as `GlobalConfig`; read by hand in the handler
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// Pays the rent for the added space
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_global_config_handler(ctx: Context<MigrateGlobalConfig>) -> Result<()> {
+    let global_config_info = ctx.accounts.global_config.to_account_info();
+    let space = 8 + GlobalConfig::INIT_SPACE;
+
+    let legacy = {
+        let data = global_config_info.try_borrow_data()?;
+        require!(data.len() < space, ErrorCode::AlreadyMigrated);
+        require!(
+            data.starts_with(GlobalConfig::DISCRIMINATOR),
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyGlobalConfig::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(legacy.authority, ctx.accounts.authority.key(), ErrorCode::Unauthorized);
+
+    realloc_account(
+        &global_config_info,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        space,
+    )?;
+
+    let mut global_config = GlobalConfig {
+        authority: legacy.authority,
+        fee_recipient: legacy.fee_recipient,
+        total_curves: legacy.total_curves,
+        ..Default::default()
+    };
+    global_config.set_default_parameters();
+    global_config.try_serialize(&mut &mut global_config_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
@@ -1,9 +1,59 @@
 pub mod initialize_global_config;
+pub mod initialize_protocol_fee_vault;
+pub mod migrate_global_config;
 pub mod create_bonding_curve;
 pub mod buy;
 pub mod sell;
+pub mod sell_all;
+pub mod sell_with_intent;
+pub mod verify_curve;
+pub mod close_user_position;
+pub mod update_global_config;
+pub mod claim_creator_fees;
+pub mod migrate_bonding_curve;
+pub mod transfer_creator;
+pub mod register_referrer;
+pub mod claim_referral_fees;
+pub mod withdraw_protocol_fees;
+pub mod set_fee_split;
+pub mod get_twap;
+pub mod initialize_price_candles;
+pub mod quote_trade;
+pub mod get_curve_state;
+pub mod swap_curves;
+pub mod place_limit_order;
+pub mod cancel_limit_order;
+pub mod execute_order;
+pub mod create_dca_order;
+pub mod execute_dca_fill;
+pub mod cancel_dca_order;
 
 pub use initialize_global_config::*;
+pub use initialize_protocol_fee_vault::*;
+pub use migrate_global_config::*;
 pub use create_bonding_curve::*;
 pub use buy::*;
-pub use sell::*;
\ No newline at end of file
+pub use sell::*;
+pub use sell_all::*;
+pub use sell_with_intent::*;
+pub use verify_curve::*;
+pub use close_user_position::*;
+pub use update_global_config::*;
+pub use claim_creator_fees::*;
+pub use migrate_bonding_curve::*;
+pub use transfer_creator::*;
+pub use register_referrer::*;
+pub use claim_referral_fees::*;
+pub use withdraw_protocol_fees::*;
+pub use set_fee_split::*;
+pub use get_twap::*;
+pub use initialize_price_candles::*;
+pub use quote_trade::*;
+pub use get_curve_state::*;
+pub use swap_curves::*;
+pub use place_limit_order::*;
+pub use cancel_limit_order::*;
+pub use execute_order::*;
+pub use create_dca_order::*;
+pub use execute_dca_fill::*;
+pub use cancel_dca_order::*;
\ No newline at end of file
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{BondingCurve, LimitOrder};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::LimitOrderPlacedEvent;
+use crate::utils::transfer_sol;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PlaceLimitOrderParams {
+    /// Caller-chosen id, unique per owner and curve
+    pub order_id: u64,
+    pub is_buy: bool,
+    /// Lamports to spend for a buy, tokens to sell for a sell
+    pub amount_in: u64,
+    pub target_price: u128,
+    pub min_amount_out: u64,
+    pub expiry_timestamp: i64,
+    pub keeper_reward: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: PlaceLimitOrderParams)]
+pub struct PlaceLimitOrder<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + LimitOrder::INIT_SPACE,
+        seeds = [
+            LIMIT_ORDER_SEED,
+            bonding_curve.key().as_ref(),
+            owner.key().as_ref(),
+            params.order_id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub limit_order: Account<'info, LimitOrder>,
+
+    /// Holds the tokens of a sell order
+    #[account(
+        init,
+        payer = owner,
+        seeds = [LIMIT_ORDER_ESCROW_SEED, limit_order.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = limit_order,
+        token::token_program = token_program,
+    )]
+    pub order_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Source of a sell order's tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn place_limit_order_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, PlaceLimitOrder<'info>>,
+    params: PlaceLimitOrderParams,
+) -> Result<()> {
+    require!(params.amount_in > 0, ErrorCode::InvalidAmount);
+    require!(params.target_price > 0, ErrorCode::InvalidAmount);
+    require!(!ctx.accounts.bonding_curve.complete, ErrorCode::CurveComplete);
+    require!(
+        params.expiry_timestamp > Clock::get()?.unix_timestamp,
+        ErrorCode::OrderExpired
+    );
+
+    let limit_order_info = ctx.accounts.limit_order.to_account_info();
+    let owner_info = ctx.accounts.owner.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+
+    // Escrow the input: SOL stays on the order account, tokens in its escrow token account
+    if params.is_buy {
+        transfer_sol(&owner_info, &limit_order_info, &system_program_info, params.amount_in)?;
+    } else {
+        let order_token_account = ctx.accounts.order_token_account
+            .as_ref()
+            .ok_or(ErrorCode::OrderTokenAccountRequired)?;
+        let owner_token_account = ctx.accounts.owner_token_account
+            .as_ref()
+            .ok_or(ErrorCode::OrderTokenAccountRequired)?;
+
+        crate::utils::perform_token_transfer(
+            &owner_token_account.to_account_info(),
+            &order_token_account.to_account_info(),
+            &owner_info,
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            params.amount_in,
+            &[],
+            ctx.remaining_accounts,
+        )?;
+    }
+    transfer_sol(&owner_info, &limit_order_info, &system_program_info, params.keeper_reward)?;
+
+    let limit_order = &mut ctx.accounts.limit_order;
+    limit_order.owner = ctx.accounts.owner.key();
+    limit_order.bonding_curve = ctx.accounts.bonding_curve.key();
+    limit_order.order_id = params.order_id;
+    limit_order.is_buy = params.is_buy;
+    limit_order.amount_in = params.amount_in;
+    limit_order.target_price = params.target_price;
+    limit_order.min_amount_out = params.min_amount_out;
+    limit_order.expiry_timestamp = params.expiry_timestamp;
+    limit_order.keeper_reward = params.keeper_reward;
+
+    emit!(LimitOrderPlacedEvent {
+        order: limit_order.key(),
+        owner: limit_order.owner,
+        mint: ctx.accounts.mint.key(),
+        is_buy: params.is_buy,
+        amount_in: params.amount_in,
+        target_price: params.target_price,
+        expiry_timestamp: params.expiry_timestamp,
+    });
+
+    Ok(())
+}
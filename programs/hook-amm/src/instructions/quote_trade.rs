@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::state::{GlobalConfig, BondingCurve};
+use crate::constants::*;
+use crate::quote::{buy_fee_rates, quote_buy, quote_buy_exact_out, quote_sell, sell_fee_rates, BuyQuote, SellQuote};
+
+/// Read-only accounts for pricing a trade. Quotes assume no referrer; the referral
+/// share is carved out of the protocol fee, so totals are the same either way.
+#[derive(Accounts)]
+pub struct Quote<'info> {
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        seeds = [CURVE_TOKEN_ACCOUNT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub curve_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub fn quote_buy_handler(ctx: Context<Quote>, sol_amount: u64) -> Result<BuyQuote> {
+    let now = Clock::get()?.unix_timestamp;
+    let fee_rates = buy_fee_rates(&ctx.accounts.bonding_curve, &ctx.accounts.global_config, now, 0)?;
+
+    quote_buy(&ctx.accounts.bonding_curve, &fee_rates, sol_amount, ctx.accounts.curve_token_account.amount)
+}
+
+pub fn quote_buy_exact_out_handler(ctx: Context<Quote>, token_amount: u64) -> Result<BuyQuote> {
+    let now = Clock::get()?.unix_timestamp;
+    let fee_rates = buy_fee_rates(&ctx.accounts.bonding_curve, &ctx.accounts.global_config, now, 0)?;
+
+    quote_buy_exact_out(&ctx.accounts.bonding_curve, &fee_rates, token_amount, ctx.accounts.curve_token_account.amount)
+}
+
+pub fn quote_sell_handler(ctx: Context<Quote>, token_amount: u64) -> Result<SellQuote> {
+    let now = Clock::get()?.unix_timestamp;
+    let fee_rates = sell_fee_rates(&ctx.accounts.bonding_curve, &ctx.accounts.global_config, now, 0)?;
+
+    quote_sell(&ctx.accounts.bonding_curve, &fee_rates, token_amount)
+}
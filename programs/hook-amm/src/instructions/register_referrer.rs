@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::Referrer;
+use crate::constants::REFERRER_SEED;
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Referrer::INIT_SPACE,
+        seeds = [REFERRER_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_referrer_handler(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referrer = &mut ctx.accounts.referrer;
+    referrer.authority = ctx.accounts.authority.key();
+    referrer.total_accrued = 0;
+    referrer.total_claimed = 0;
+    referrer.referred_volume = 0;
+    referrer.trade_count = 0;
+    Ok(())
+}
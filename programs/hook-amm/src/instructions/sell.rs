@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{GlobalConfig, BondingCurve};
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, AssociatedToken};
+use crate::state::{GlobalConfig, BondingCurve, CreatorFeeVault, ProtocolFeeVault, Referrer, UserPosition, PriceCandles};
 use crate::constants::*;
 use crate::errors::ErrorCode;
-use crate::events::TradeEvent;
-use crate::utils::calculate_sell_amount;
+use crate::quote::{quote_sell, sell_fee_rates, SellQuote};
+use crate::utils::{settle_sell, transfer_lamports, TradeSettlement};
 
 #[derive(Accounts)]
 pub struct Sell<'info> {
@@ -23,17 +24,29 @@ pub struct Sell<'info> {
     )]
     pub curve_token_account: InterfaceAccount<'info, TokenAccount>,
     
+    /// Associated token account of `owner`, or of `user` when selling their own tokens
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = user,
-        associated_token::token_program = token_program,
+        address = get_associated_token_address_with_program_id(
+            &owner.as_ref().map_or(user.key(), |owner| owner.key()),
+            &mint.key(),
+            &token_program.key(),
+        ) @ ErrorCode::Unauthorized,
     )]
     pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
+    /// Token owner, or an approved delegate of `user_token_account`
     #[account(mut)]
     pub user: Signer<'info>,
     
+    /// CHECK: Token owner when `user` signs as delegate; always receives the proceeds
+    #[account(mut)]
+    pub owner: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Receives the sale proceeds; defaults to `user`. Not allowed on delegated sells
+    #[account(mut)]
+    pub sol_recipient: Option<UncheckedAccount<'info>>,
+    
     pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
@@ -42,12 +55,45 @@ pub struct Sell<'info> {
     )]
     pub global_config: Account<'info, GlobalConfig>,
     
-    /// CHECK: Fee recipient from global config
     #[account(
         mut,
-        constraint = fee_recipient.key() == global_config.fee_recipient
+        seeds = [PROTOCOL_FEE_VAULT_SEED],
+        bump
+    )]
+    pub protocol_fee_vault: Account<'info, ProtocolFeeVault>,
+    
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_VAULT_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+    
+    /// Optional frontend referrer credited with a share of the protocol fee
+    #[account(
+        mut,
+        seeds = [REFERRER_SEED, referrer.authority.as_ref()],
+        bump
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+    
+    /// Optional trading history for the user on this curve; not accepted on delegated sells
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [USER_POSITION_SEED, bonding_curve.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>,
+    
+    /// Optional OHLCV candles for this curve, required once created with `initialize_price_candles`
+    #[account(
+        mut,
+        seeds = [PRICE_CANDLES_SEED, bonding_curve.key().as_ref()],
+        bump
     )]
-    pub fee_recipient: AccountInfo<'info>,
+    pub price_candles: Option<AccountLoader<'info, PriceCandles>>,
     
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -58,31 +104,55 @@ pub struct Sell<'info> {
 pub fn sell_handler<'info>(
     ctx: Context<'_, '_, '_, 'info, Sell<'info>>, 
     token_amount: u64, 
-    min_sol_amount: u64
-) -> Result<()> {
+    min_sol_amount: u64,
+    close_token_account: bool,
+) -> Result<SellQuote> {
     require!(token_amount > 0, ErrorCode::InvalidAmount);
     require!(!ctx.accounts.bonding_curve.complete, ErrorCode::CurveComplete);
     
-    // Calculate output amount using constant product formula
-    let sol_amount = calculate_sell_amount(
-        token_amount,
-        ctx.accounts.bonding_curve.virtual_token_reserves + ctx.accounts.bonding_curve.token_total_supply - ctx.accounts.bonding_curve.real_token_reserves,
-        ctx.accounts.bonding_curve.virtual_sol_reserves + ctx.accounts.bonding_curve.real_sol_reserves,
-    )?;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    
+    // A delegate may sell up to its allowance, with proceeds going to the owner
+    let token_owner = ctx.accounts.user_token_account.owner;
+    let is_delegated = token_owner != ctx.accounts.user.key();
+    if is_delegated {
+        let owner = ctx.accounts.owner.as_ref().ok_or(ErrorCode::Unauthorized)?;
+        require_keys_eq!(owner.key(), token_owner, ErrorCode::Unauthorized);
+        require!(ctx.accounts.sol_recipient.is_none(), ErrorCode::Unauthorized);
+        require!(ctx.accounts.user_position.is_none(), ErrorCode::Unauthorized);
+        if ctx.accounts.user_token_account.delegate != COption::Some(ctx.accounts.user.key())
+            || ctx.accounts.user_token_account.delegated_amount < token_amount
+        {
+            msg!(
+                "Delegate not approved: delegate={:?} delegated_amount={} token_amount={}",
+                ctx.accounts.user_token_account.delegate,
+                ctx.accounts.user_token_account.delegated_amount,
+                token_amount
+            );
+            return err!(ErrorCode::Unauthorized);
+        }
+    }
     
-    let fee_amount = sol_amount
-        .checked_mul(FEE_BASIS_POINTS as u64)
-        .unwrap()
-        .checked_div(10000)
-        .unwrap();
-    let sol_amount_after_fee = sol_amount.checked_sub(fee_amount).unwrap();
+    let referral_share_bps = match ctx.accounts.referrer.as_ref() {
+        Some(referrer) => {
+            require!(referrer.authority != ctx.accounts.user.key(), ErrorCode::SelfReferral);
+            ctx.accounts.global_config.referral_share_bps
+        }
+        None => 0,
+    };
+    let fee_rates = sell_fee_rates(&ctx.accounts.bonding_curve, &ctx.accounts.global_config, now, referral_share_bps)?;
+    
+    // Calculate output amount using constant product formula
+    let quote = quote_sell(&ctx.accounts.bonding_curve, &fee_rates, token_amount)?;
+    let sol_amount_after_fee = quote.sol_amount_after_fee;
     
     require!(sol_amount_after_fee >= min_sol_amount, ErrorCode::SlippageExceeded);
     
     // Transfer tokens from seller to curve (handles Token-2022 with hooks)
     crate::utils::perform_token_transfer(
-        &ctx.accounts.user_token_account,
-        &ctx.accounts.curve_token_account,
+        &ctx.accounts.user_token_account.to_account_info(),
+        &ctx.accounts.curve_token_account.to_account_info(),
         &ctx.accounts.user.to_account_info(),
         &ctx.accounts.token_program,
         &ctx.accounts.mint,
@@ -91,33 +161,55 @@ pub fn sell_handler<'info>(
         ctx.remaining_accounts,
     )?;
     
-    // Update reserves - for sell: SOL decreases, tokens increase (tokens are returned to pool)
-    ctx.accounts.bonding_curve.real_sol_reserves = ctx.accounts.bonding_curve.real_sol_reserves
-        .checked_sub(sol_amount)
-        .unwrap();
-    ctx.accounts.bonding_curve.real_token_reserves = ctx.accounts.bonding_curve.real_token_reserves
-        .checked_sub(token_amount)
-        .unwrap();
-    
-    // Transfer SOL from curve to seller
-    **ctx.accounts.bonding_curve.to_account_info().try_borrow_mut_lamports()? -= sol_amount_after_fee;
-    **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += sol_amount_after_fee;
-    
-    // Transfer fee to fee recipient
-    if fee_amount > 0 {
-        **ctx.accounts.bonding_curve.to_account_info().try_borrow_mut_lamports()? -= fee_amount;
-        **ctx.accounts.fee_recipient.to_account_info().try_borrow_mut_lamports()? += fee_amount;
+    // Return the token account's rent when the whole balance was sold; only the owner can close it
+    if close_token_account {
+        require!(!is_delegated, ErrorCode::TokenAccountNotClosable);
+        ctx.accounts.user_token_account.reload()?;
+        if ctx.accounts.user_token_account.amount != 0 {
+            msg!(
+                "Token account not empty after sell: amount={}",
+                ctx.accounts.user_token_account.amount
+            );
+            return err!(ErrorCode::TokenAccountNotClosable);
+        }
+        token_interface::close_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.user_token_account.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ))?;
     }
     
-    emit!(TradeEvent {
-        mint: ctx.accounts.mint.key(),
-        user: ctx.accounts.user.key(),
-        sol_amount: sol_amount_after_fee,
-        token_amount,
-        is_buy: false,
-        virtual_sol_reserves: ctx.accounts.bonding_curve.virtual_sol_reserves + ctx.accounts.bonding_curve.real_sol_reserves,
-        virtual_token_reserves: ctx.accounts.bonding_curve.virtual_token_reserves + ctx.accounts.bonding_curve.token_total_supply - ctx.accounts.bonding_curve.real_token_reserves,
-    });
+    // Transfer SOL from curve to seller, or to the chosen recipient
+    let sol_recipient_info = match (ctx.accounts.owner.as_ref(), ctx.accounts.sol_recipient.as_ref()) {
+        (Some(owner), _) if is_delegated => owner.to_account_info(),
+        (_, Some(sol_recipient)) => sol_recipient.to_account_info(),
+        _ => ctx.accounts.user.to_account_info(),
+    };
+    transfer_lamports(&ctx.accounts.bonding_curve.to_account_info(), &sol_recipient_info, sol_amount_after_fee)?;
+    
+    // Update reserves, move the fees, and record the trade. Positions track the
+    // signer's own trades, so delegated sells are not recorded
+    settle_sell(
+        TradeSettlement {
+            mint: ctx.accounts.mint.key(),
+            global_config: &ctx.accounts.global_config,
+            bonding_curve: &mut ctx.accounts.bonding_curve,
+            protocol_fee_vault: &mut ctx.accounts.protocol_fee_vault,
+            creator_fee_vault: &mut ctx.accounts.creator_fee_vault,
+            referrer: ctx.accounts.referrer.as_mut(),
+            price_candles: ctx.accounts.price_candles.as_ref(),
+            user_position: ctx.accounts.user_position.as_mut(),
+            user: token_owner,
+            recipient: sol_recipient_info.key(),
+            now,
+            slot: clock.slot,
+        },
+        &quote,
+        sol_amount_after_fee,
+    )?;
     
-    Ok(())
+    Ok(quote)
 }
\ No newline at end of file
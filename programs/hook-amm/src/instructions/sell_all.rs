@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::instructions::{sell_handler, Sell};
+use crate::quote::SellQuote;
+
+/// Sells the seller's entire balance, read on-chain so incoming transfers can't race it.
+/// A delegate sells up to its remaining allowance.
+pub fn sell_all_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
+    min_sol_amount: u64,
+    close_token_account: bool,
+) -> Result<SellQuote> {
+    let user_token_account = &ctx.accounts.user_token_account;
+    let token_amount = if user_token_account.owner == ctx.accounts.user.key() {
+        user_token_account.amount
+    } else {
+        user_token_account.amount.min(user_token_account.delegated_amount)
+    };
+    require!(token_amount > 0, ErrorCode::InsufficientBalance);
+
+    sell_handler(ctx, token_amount, min_sol_amount, close_token_account)
+}
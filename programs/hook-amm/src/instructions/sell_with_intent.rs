@@ -0,0 +1,266 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{GlobalConfig, BondingCurve, CreatorFeeVault, ProtocolFeeVault, IntentNonce, PriceCandles};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::IntentExecutedEvent;
+use crate::quote::{quote_sell, sell_fee_rates, SellQuote};
+use crate::utils::{settle_sell, transfer_lamports, TradeSettlement};
+
+/// Sell order signed off-chain by the token owner. The signed message is
+/// `SELL_INTENT_DOMAIN` followed by the Borsh encoding of this struct.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SellIntent {
+    pub bonding_curve: Pubkey,
+    pub token_amount: u64,
+    /// Minimum SOL the user receives, after the relayer fee
+    pub min_sol_amount: u64,
+    /// Lamports paid to the relayer out of the proceeds
+    pub relayer_fee: u64,
+    pub deadline: i64,
+    pub nonce: u64,
+}
+
+/// Executes a user's signed sell intent on behalf of a relayer, who pays the
+/// transaction fee. The transaction must carry an ed25519 program instruction
+/// verifying the user's signature immediately before this one.
+///
+/// Tokens move under the `intent_authority` PDA, which the user approves once as
+/// delegate of their token account (that approval can itself be fee-paid by a relayer).
+///
+/// Proceeds are paid to the user's system account by direct lamport transfer, so
+/// the user's balance after the sell must be at least the rent-exempt minimum for
+/// a zero-data account; intents whose proceeds fall short are rejected.
+#[derive(Accounts)]
+pub struct SellWithIntent<'info> {
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [CURVE_TOKEN_ACCOUNT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Token owner; authorized by the ed25519 signature over the intent
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: PDA approved by the user as delegate of their token account
+    #[account(
+        seeds = [INTENT_AUTHORITY_SEED],
+        bump
+    )]
+    pub intent_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + IntentNonce::INIT_SPACE,
+        seeds = [INTENT_NONCE_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub intent_nonce: Box<Account<'info, IntentNonce>>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_FEE_VAULT_SEED],
+        bump
+    )]
+    pub protocol_fee_vault: Box<Account<'info, ProtocolFeeVault>>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_VAULT_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Box<Account<'info, CreatorFeeVault>>,
+
+    /// Optional OHLCV candles for this curve, required once created with `initialize_price_candles`
+    #[account(
+        mut,
+        seeds = [PRICE_CANDLES_SEED, bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub price_candles: Option<AccountLoader<'info, PriceCandles>>,
+
+    /// CHECK: Instructions sysvar, used to read the ed25519 verification
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn sell_with_intent_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SellWithIntent<'info>>,
+    intent: SellIntent,
+) -> Result<SellQuote> {
+    require!(intent.token_amount > 0, ErrorCode::InvalidAmount);
+    require!(!ctx.accounts.bonding_curve.complete, ErrorCode::CurveComplete);
+    require_keys_eq!(intent.bonding_curve, ctx.accounts.bonding_curve.key(), ErrorCode::InvalidIntentSignature);
+
+    let mut message = SELL_INTENT_DOMAIN.to_vec();
+    intent.serialize(&mut message)?;
+    verify_ed25519_instruction(&ctx.accounts.instructions, &ctx.accounts.user.key(), &message)?;
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(now <= intent.deadline, ErrorCode::IntentExpired);
+
+    // Consume the nonce so the intent cannot be replayed
+    let intent_nonce = &mut ctx.accounts.intent_nonce;
+    if intent.nonce != intent_nonce.nonce {
+        msg!("Invalid intent nonce: expected={} got={}", intent_nonce.nonce, intent.nonce);
+        return err!(ErrorCode::InvalidIntentNonce);
+    }
+    intent_nonce.user = ctx.accounts.user.key();
+    intent_nonce.nonce = intent_nonce.nonce.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+    let fee_rates = sell_fee_rates(&ctx.accounts.bonding_curve, &ctx.accounts.global_config, now, 0)?;
+    let quote = quote_sell(&ctx.accounts.bonding_curve, &fee_rates, intent.token_amount)?;
+    let user_sol_amount = quote.sol_amount_after_fee.checked_sub(intent.relayer_fee).ok_or_else(|| {
+        msg!(
+            "Relayer fee exceeds proceeds: sol_amount_after_fee={} relayer_fee={}",
+            quote.sol_amount_after_fee,
+            intent.relayer_fee
+        );
+        error!(ErrorCode::SlippageExceeded)
+    })?;
+    require!(user_sol_amount >= intent.min_sol_amount, ErrorCode::SlippageExceeded);
+
+    // A wallet funded only by this payout must end up rent exempt
+    let user_lamports_after = ctx.accounts.user.lamports()
+        .checked_add(user_sol_amount)
+        .ok_or(ErrorCode::Overflow)?;
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    if user_lamports_after < rent_exempt_minimum {
+        msg!(
+            "Intent proceeds below rent: user_lamports_after={} rent_exempt_minimum={}",
+            user_lamports_after,
+            rent_exempt_minimum
+        );
+        return err!(ErrorCode::IntentProceedsBelowRent);
+    }
+
+    // Move the tokens as the user's approved delegate
+    let signer_seeds: &[&[u8]] = &[INTENT_AUTHORITY_SEED, &[ctx.bumps.intent_authority]];
+    crate::utils::perform_token_transfer(
+        &ctx.accounts.user_token_account.to_account_info(),
+        &ctx.accounts.curve_token_account.to_account_info(),
+        &ctx.accounts.intent_authority.to_account_info(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        intent.token_amount,
+        &[signer_seeds],
+        ctx.remaining_accounts,
+    )?;
+
+    let bonding_curve_info = ctx.accounts.bonding_curve.to_account_info();
+    transfer_lamports(&bonding_curve_info, &ctx.accounts.user.to_account_info(), user_sol_amount)?;
+    transfer_lamports(&bonding_curve_info, &ctx.accounts.relayer.to_account_info(), intent.relayer_fee)?;
+
+    let user_key = ctx.accounts.user.key();
+    settle_sell(
+        TradeSettlement {
+            mint: ctx.accounts.mint.key(),
+            global_config: &ctx.accounts.global_config,
+            bonding_curve: &mut ctx.accounts.bonding_curve,
+            protocol_fee_vault: &mut ctx.accounts.protocol_fee_vault,
+            creator_fee_vault: &mut ctx.accounts.creator_fee_vault,
+            referrer: None,
+            price_candles: ctx.accounts.price_candles.as_ref(),
+            user_position: None,
+            user: user_key,
+            recipient: user_key,
+            now,
+            slot: clock.slot,
+        },
+        &quote,
+        user_sol_amount,
+    )?;
+
+    emit!(IntentExecutedEvent {
+        user: user_key,
+        relayer: ctx.accounts.relayer.key(),
+        nonce: intent.nonce,
+        relayer_fee: intent.relayer_fee,
+    });
+
+    Ok(quote)
+}
+
+/// Checks that the previous instruction is an ed25519 program verification of
+/// exactly `message` signed by `signer`, with all data inline in that instruction.
+fn verify_ed25519_instruction(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    const HEADER_LEN: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    const PUBKEY_LEN: usize = 32;
+
+    let current_index = load_current_index_checked(instructions)?;
+    require!(current_index > 0, ErrorCode::InvalidIntentSignature);
+    let ed25519_ix = load_instruction_at_checked(current_index as usize - 1, instructions)?;
+    require_keys_eq!(ed25519_ix.program_id, ed25519_program::ID, ErrorCode::InvalidIntentSignature);
+
+    let data = &ed25519_ix.data;
+    require!(
+        data.len() >= HEADER_LEN + OFFSETS_LEN && data[0] == 1,
+        ErrorCode::InvalidIntentSignature
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[HEADER_LEN + at], data[HEADER_LEN + at + 1]]);
+    let signature_instruction_index = read_u16(2);
+    let public_key_offset = read_u16(4) as usize;
+    let public_key_instruction_index = read_u16(6);
+    let message_offset = read_u16(8) as usize;
+    let message_size = read_u16(10) as usize;
+    let message_instruction_index = read_u16(12);
+
+    // Offsets must point into the ed25519 instruction itself
+    require!(
+        signature_instruction_index == u16::MAX
+            && public_key_instruction_index == u16::MAX
+            && message_instruction_index == u16::MAX,
+        ErrorCode::InvalidIntentSignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + PUBKEY_LEN)
+        .ok_or(ErrorCode::InvalidIntentSignature)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidIntentSignature)?;
+
+    require!(
+        public_key == signer.as_ref() && signed_message == message,
+        ErrorCode::InvalidIntentSignature
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, FeeSplit, FeeSplitRecipient};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeSplit::INIT_SPACE,
+        seeds = [FEE_SPLIT_SEED],
+        bump
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// An empty `recipients` list disables the split and sends withdrawals to `fee_recipient`.
+pub fn set_fee_split_handler(
+    ctx: Context<SetFeeSplit>,
+    recipients: Vec<FeeSplitRecipient>,
+    dust_recipient_index: u8,
+) -> Result<()> {
+    FeeSplit::validate(&recipients, dust_recipient_index)?;
+
+    ctx.accounts.global_config.fee_split_enabled = !recipients.is_empty();
+
+    let fee_split = &mut ctx.accounts.fee_split;
+    fee_split.recipients = recipients;
+    fee_split.dust_recipient_index = dust_recipient_index;
+
+    Ok(())
+}
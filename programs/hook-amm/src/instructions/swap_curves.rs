@@ -0,0 +1,249 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken};
+use crate::state::{GlobalConfig, BondingCurve, CreatorFeeVault, ProtocolFeeVault, PriceCandles};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::quote::{buy_fee_rates, quote_buy, quote_sell, sell_fee_rates, BuyQuote, SellQuote};
+use crate::utils::{settle_buy, settle_sell, transfer_lamports, BuyFunding, TradeSettlement};
+
+#[derive(Accounts)]
+pub struct SwapCurves<'info> {
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint_a.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve_a: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [CURVE_TOKEN_ACCOUNT_SEED, mint_a.key().as_ref()],
+        bump,
+    )]
+    pub curve_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = user,
+        associated_token::token_program = token_program_a,
+    )]
+    pub user_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_VAULT_SEED, bonding_curve_a.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault_a: Box<Account<'info, CreatorFeeVault>>,
+
+    /// Optional OHLCV candles for this curve, required once created with `initialize_price_candles`
+    #[account(
+        mut,
+        seeds = [PRICE_CANDLES_SEED, bonding_curve_a.key().as_ref()],
+        bump
+    )]
+    pub price_candles_a: Option<AccountLoader<'info, PriceCandles>>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint_b.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve_b: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [CURVE_TOKEN_ACCOUNT_SEED, mint_b.key().as_ref()],
+        bump,
+    )]
+    pub curve_token_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Associated token account of `user` for `mint_b`; created if missing
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &user.key(),
+            &mint_b.key(),
+            &token_program_b.key(),
+        ) @ ErrorCode::InvalidRecipient,
+    )]
+    pub user_token_account_b: UncheckedAccount<'info>,
+
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_VAULT_SEED, bonding_curve_b.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault_b: Box<Account<'info, CreatorFeeVault>>,
+
+    /// Optional OHLCV candles for this curve, required once created with `initialize_price_candles`
+    #[account(
+        mut,
+        seeds = [PRICE_CANDLES_SEED, bonding_curve_b.key().as_ref()],
+        bump
+    )]
+    pub price_candles_b: Option<AccountLoader<'info, PriceCandles>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_FEE_VAULT_SEED],
+        bump
+    )]
+    pub protocol_fee_vault: Box<Account<'info, ProtocolFeeVault>>,
+
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SwapResult {
+    pub sell: SellQuote,
+    pub buy: BuyQuote,
+}
+
+/// Sells `token_amount_in` of A and buys B with the proceeds. `remaining_accounts`
+/// holds A's transfer hook accounts first (`hook_accounts_a` of them), then B's.
+pub fn swap_curves_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SwapCurves<'info>>,
+    token_amount_in: u64,
+    min_token_amount_out: u64,
+    hook_accounts_a: u8,
+) -> Result<SwapResult> {
+    require!(token_amount_in > 0, ErrorCode::InvalidAmount);
+    require!(ctx.accounts.mint_a.key() != ctx.accounts.mint_b.key(), ErrorCode::InvalidSwapRoute);
+    require!(!ctx.accounts.bonding_curve_a.complete, ErrorCode::CurveComplete);
+    require!(!ctx.accounts.bonding_curve_b.complete, ErrorCode::CurveComplete);
+    require!(
+        hook_accounts_a as usize <= ctx.remaining_accounts.len(),
+        ErrorCode::InvalidSwapRoute
+    );
+    let (remaining_accounts_a, remaining_accounts_b) = ctx.remaining_accounts.split_at(hook_accounts_a as usize);
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(
+        now >= ctx.accounts.bonding_curve_b.trading_start_timestamp,
+        ErrorCode::TradingNotStarted
+    );
+    ctx.accounts.bonding_curve_b.require_launch_window_closed(now)?;
+
+    // Leg A: sell into SOL
+    let sell_fee_rates = sell_fee_rates(&ctx.accounts.bonding_curve_a, &ctx.accounts.global_config, now, 0)?;
+    let sell = quote_sell(&ctx.accounts.bonding_curve_a, &sell_fee_rates, token_amount_in)?;
+
+    let user_info = ctx.accounts.user.to_account_info();
+    crate::utils::perform_token_transfer(
+        &ctx.accounts.user_token_account_a.to_account_info(),
+        &ctx.accounts.curve_token_account_a.to_account_info(),
+        &user_info,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.mint_a,
+        token_amount_in,
+        &[],
+        remaining_accounts_a,
+    )?;
+
+    transfer_lamports(&ctx.accounts.bonding_curve_a.to_account_info(), &user_info, sell.sol_amount_after_fee)?;
+    settle_sell(
+        TradeSettlement {
+            mint: ctx.accounts.mint_a.key(),
+            global_config: &ctx.accounts.global_config,
+            bonding_curve: &mut ctx.accounts.bonding_curve_a,
+            protocol_fee_vault: &mut ctx.accounts.protocol_fee_vault,
+            creator_fee_vault: &mut ctx.accounts.creator_fee_vault_a,
+            referrer: None,
+            price_candles: ctx.accounts.price_candles_a.as_ref(),
+            user_position: None,
+            user: user_info.key(),
+            recipient: user_info.key(),
+            now,
+            slot: clock.slot,
+        },
+        &sell,
+        sell.sol_amount_after_fee,
+    )?;
+
+    // Leg B: buy with the proceeds; any partial-fill refund stays with the user
+    let buy_fee_rates = buy_fee_rates(&ctx.accounts.bonding_curve_b, &ctx.accounts.global_config, now, 0)?;
+    let buy = quote_buy(
+        &ctx.accounts.bonding_curve_b,
+        &buy_fee_rates,
+        sell.sol_amount_after_fee,
+        ctx.accounts.curve_token_account_b.amount,
+    )?;
+
+    require!(buy.token_amount >= min_token_amount_out, ErrorCode::SlippageExceeded);
+
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    settle_buy(
+        TradeSettlement {
+            mint: ctx.accounts.mint_b.key(),
+            global_config: &ctx.accounts.global_config,
+            bonding_curve: &mut ctx.accounts.bonding_curve_b,
+            protocol_fee_vault: &mut ctx.accounts.protocol_fee_vault,
+            creator_fee_vault: &mut ctx.accounts.creator_fee_vault_b,
+            referrer: None,
+            price_candles: ctx.accounts.price_candles_b.as_ref(),
+            user_position: None,
+            user: user_info.key(),
+            recipient: user_info.key(),
+            now,
+            slot: clock.slot,
+        },
+        &buy,
+        BuyFunding::Payer {
+            payer: &user_info,
+            system_program: &system_program_info,
+        },
+    )?;
+
+    // Create the user's B token account on first purchase
+    associated_token::create_idempotent(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: user_info.clone(),
+            associated_token: ctx.accounts.user_token_account_b.to_account_info(),
+            authority: user_info.clone(),
+            mint: ctx.accounts.mint_b.to_account_info(),
+            system_program: system_program_info.clone(),
+            token_program: ctx.accounts.token_program_b.to_account_info(),
+        },
+    ))?;
+
+    let mint_b_key = ctx.accounts.mint_b.key();
+    let signer_seeds = &[
+        BONDING_CURVE_SEED,
+        mint_b_key.as_ref(),
+        &[ctx.bumps.bonding_curve_b],
+    ];
+
+    crate::utils::perform_token_transfer(
+        &ctx.accounts.curve_token_account_b.to_account_info(),
+        &ctx.accounts.user_token_account_b.to_account_info(),
+        &ctx.accounts.bonding_curve_b.to_account_info(),
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.mint_b,
+        buy.token_amount,
+        &[signer_seeds],
+        remaining_accounts_b,
+    )?;
+
+    Ok(SwapResult { sell, buy })
+}
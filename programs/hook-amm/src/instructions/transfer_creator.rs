@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::BondingCurve;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::CreatorTransferredEvent;
+
+#[derive(Accounts)]
+pub struct TransferCreator<'info> {
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        has_one = creator @ ErrorCode::Unauthorized
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn transfer_creator_handler(ctx: Context<TransferCreator>, new_creator: Pubkey) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    let previous_creator = bonding_curve.creator;
+    bonding_curve.creator = new_creator;
+
+    emit!(CreatorTransferredEvent {
+        mint: ctx.accounts.mint.key(),
+        previous_creator,
+        new_creator,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::state::{DynamicFeeConfig, FeeTier, GlobalConfig};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct UpdateGlobalConfig<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Fields left as `None` keep their current value.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateGlobalConfigParams {
+    pub new_authority: Option<Pubkey>,
+    pub fee_recipient: Option<Pubkey>,
+    pub min_creator_fee_bps: Option<u16>,
+    pub max_creator_fee_bps: Option<u16>,
+    pub referral_share_bps: Option<u16>,
+    /// Replaces the whole tier table; existing curves keep the fees they were created with
+    pub fee_tiers: Option<Vec<FeeTier>>,
+    pub dynamic_fee: Option<DynamicFeeConfig>,
+    pub max_price_impact_bps: Option<u16>,
+    pub max_slot_price_move_bps: Option<u16>,
+}
+
+pub fn update_global_config_handler(
+    ctx: Context<UpdateGlobalConfig>,
+    params: UpdateGlobalConfigParams
+) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+
+    if let Some(new_authority) = params.new_authority {
+        global_config.authority = new_authority;
+    }
+    if let Some(fee_recipient) = params.fee_recipient {
+        global_config.fee_recipient = fee_recipient;
+    }
+    if let Some(min_creator_fee_bps) = params.min_creator_fee_bps {
+        global_config.min_creator_fee_bps = min_creator_fee_bps;
+    }
+    if let Some(max_creator_fee_bps) = params.max_creator_fee_bps {
+        global_config.max_creator_fee_bps = max_creator_fee_bps;
+    }
+    if let Some(referral_share_bps) = params.referral_share_bps {
+        global_config.referral_share_bps = referral_share_bps;
+    }
+    if let Some(fee_tiers) = params.fee_tiers {
+        require!(
+            !fee_tiers.is_empty() && fee_tiers.len() <= MAX_FEE_TIERS,
+            ErrorCode::InvalidFee
+        );
+        require!(
+            fee_tiers.iter().all(|tier| {
+                tier.buy_fee_bps <= MAX_PROTOCOL_FEE_BPS && tier.sell_fee_bps <= MAX_PROTOCOL_FEE_BPS
+            }),
+            ErrorCode::InvalidFee
+        );
+        global_config.fee_tiers = fee_tiers;
+    }
+    if let Some(dynamic_fee) = params.dynamic_fee {
+        require!(
+            dynamic_fee.max_dynamic_fee_bps <= MAX_DYNAMIC_FEE_BPS
+                && (dynamic_fee.max_dynamic_fee_bps == 0 || dynamic_fee.decay_window_seconds > 0),
+            ErrorCode::InvalidFee
+        );
+        global_config.dynamic_fee = dynamic_fee;
+    }
+    if let Some(max_price_impact_bps) = params.max_price_impact_bps {
+        global_config.max_price_impact_bps = max_price_impact_bps;
+    }
+    if let Some(max_slot_price_move_bps) = params.max_slot_price_move_bps {
+        global_config.max_slot_price_move_bps = max_slot_price_move_bps;
+    }
+
+    require!(
+        global_config.min_creator_fee_bps <= global_config.max_creator_fee_bps
+            && global_config.max_creator_fee_bps <= MAX_CREATOR_FEE_BPS_LIMIT,
+        ErrorCode::InvalidFee
+    );
+    require!(
+        global_config.referral_share_bps as u64 <= FEE_DENOMINATOR,
+        ErrorCode::InvalidFee
+    );
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::state::BondingCurve;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::CurveVerificationEvent;
+use crate::utils::expected_curve_lamports;
+
+#[derive(Accounts)]
+pub struct VerifyCurve<'info> {
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        seeds = [CURVE_TOKEN_ACCOUNT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub curve_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CurveVerification {
+    pub expected_lamports: u64,
+    pub actual_lamports: u64,
+    pub expected_token_balance: u64,
+    pub actual_token_balance: u64,
+    /// Lamports cover rent plus `real_sol_reserves`
+    pub is_solvent: bool,
+    /// Both balances match the bookkeeping exactly
+    pub is_consistent: bool,
+}
+
+pub fn verify_curve_handler(ctx: Context<VerifyCurve>) -> Result<CurveVerification> {
+    let bonding_curve = &ctx.accounts.bonding_curve;
+
+    let expected_lamports = expected_curve_lamports(bonding_curve)?;
+    let actual_lamports = bonding_curve.to_account_info().lamports();
+
+    // real_token_reserves tracks tokens taken out of the curve
+    let expected_token_balance = bonding_curve.token_total_supply
+        .checked_sub(bonding_curve.real_token_reserves)
+        .ok_or_else(|| {
+            msg!(
+                "Token balance underflow: token_total_supply={} real_token_reserves={}",
+                bonding_curve.token_total_supply,
+                bonding_curve.real_token_reserves
+            );
+            error!(ErrorCode::ReserveUnderflow)
+        })?;
+    let actual_token_balance = ctx.accounts.curve_token_account.amount;
+
+    let is_solvent = actual_lamports >= expected_lamports
+        && actual_token_balance >= expected_token_balance;
+    let is_consistent = actual_lamports == expected_lamports
+        && actual_token_balance == expected_token_balance;
+
+    if !is_consistent {
+        msg!(
+            "Curve mismatch: lamports={} expected={} token_balance={} expected={}",
+            actual_lamports,
+            expected_lamports,
+            actual_token_balance,
+            expected_token_balance
+        );
+    }
+
+    emit!(CurveVerificationEvent {
+        mint: ctx.accounts.mint.key(),
+        expected_lamports,
+        actual_lamports,
+        expected_token_balance,
+        actual_token_balance,
+        is_solvent,
+        is_consistent,
+    });
+
+    Ok(CurveVerification {
+        expected_lamports,
+        actual_lamports,
+        expected_token_balance,
+        actual_token_balance,
+        is_solvent,
+        is_consistent,
+    })
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, FeeSplit, ProtocolFeeVault};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::ProtocolFeesWithdrawnEvent;
+use crate::utils::transfer_lamports;
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_FEE_VAULT_SEED],
+        bump
+    )]
+    pub protocol_fee_vault: Account<'info, ProtocolFeeVault>,
+
+    /// Required when the global config has a fee split enabled
+    #[account(
+        seeds = [FEE_SPLIT_SEED],
+        bump
+    )]
+    pub fee_split: Option<Account<'info, FeeSplit>>,
+
+    /// CHECK: Fee recipient from global config; required when no fee split is enabled
+    #[account(mut)]
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// With a fee split enabled, `remaining_accounts` must hold the split recipients in order.
+pub fn withdraw_protocol_fees_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawProtocolFees<'info>>
+) -> Result<()> {
+    let amount = ctx.accounts.protocol_fee_vault.withdrawable();
+    require!(amount > 0, ErrorCode::NothingToClaim);
+
+    let protocol_fee_vault_info = ctx.accounts.protocol_fee_vault.to_account_info();
+
+    if ctx.accounts.global_config.fee_split_enabled {
+        let fee_split = ctx.accounts.fee_split
+            .as_ref()
+            .ok_or(ErrorCode::FeeSplitRecipientMismatch)?;
+        require!(
+            ctx.remaining_accounts.len() == fee_split.recipients.len(),
+            ErrorCode::FeeSplitRecipientMismatch
+        );
+
+        let shares = fee_split.split(amount)?;
+        for ((entry, share), recipient_info) in fee_split.recipients
+            .iter()
+            .zip(shares)
+            .zip(ctx.remaining_accounts.iter())
+        {
+            require_keys_eq!(recipient_info.key(), entry.recipient, ErrorCode::FeeSplitRecipientMismatch);
+            transfer_lamports(&protocol_fee_vault_info, recipient_info, share)?;
+
+            emit!(ProtocolFeesWithdrawnEvent {
+                fee_recipient: entry.recipient,
+                amount: share,
+            });
+        }
+    } else {
+        let fee_recipient = ctx.accounts.fee_recipient
+            .as_ref()
+            .ok_or(ErrorCode::FeeRecipientRequired)?;
+        require_keys_eq!(
+            fee_recipient.key(),
+            ctx.accounts.global_config.fee_recipient,
+            ErrorCode::Unauthorized
+        );
+
+        transfer_lamports(&protocol_fee_vault_info, &fee_recipient.to_account_info(), amount)?;
+
+        emit!(ProtocolFeesWithdrawnEvent {
+            fee_recipient: fee_recipient.key(),
+            amount,
+        });
+    }
+
+    let protocol_fee_vault = &mut ctx.accounts.protocol_fee_vault;
+    protocol_fee_vault.total_withdrawn = protocol_fee_vault.total_withdrawn
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    Ok(())
+}
@@ -4,10 +4,13 @@ pub mod constants;
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod quote;
 pub mod state;
 pub mod utils;
 
 use instructions::*;
+use quote::{BuyQuote, SellQuote};
+use state::FeeSplitRecipient;
 
 declare_id!("gQJapJpSreNmTsTNT51qHC7oTJTA6JE5hJqYNiR214F");
 
@@ -19,6 +22,14 @@ pub mod hook_amm {
         initialize_global_config_handler(ctx)
     }
 
+    pub fn initialize_protocol_fee_vault(ctx: Context<InitializeProtocolFeeVault>) -> Result<()> {
+        initialize_protocol_fee_vault_handler(ctx)
+    }
+
+    pub fn migrate_global_config(ctx: Context<MigrateGlobalConfig>) -> Result<()> {
+        migrate_global_config_handler(ctx)
+    }
+
     pub fn create_bonding_curve<'info>(
         ctx: Context<'_, '_, '_, 'info, CreateBondingCurve<'info>>,
         params: CreateBondingCurveParams,
@@ -30,15 +41,140 @@ pub mod hook_amm {
         ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
         sol_amount: u64,
         min_token_amount: u64
-    ) -> Result<()> {
+    ) -> Result<BuyQuote> {
         buy_handler(ctx, sol_amount, min_token_amount)
     }
 
     pub fn sell<'info>(
         ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
         token_amount: u64,
-        min_sol_amount: u64
+        min_sol_amount: u64,
+        close_token_account: bool,
+    ) -> Result<SellQuote> {
+        sell_handler(ctx, token_amount, min_sol_amount, close_token_account)
+    }
+
+    pub fn sell_all<'info>(
+        ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
+        min_sol_amount: u64,
+        close_token_account: bool,
+    ) -> Result<SellQuote> {
+        sell_all_handler(ctx, min_sol_amount, close_token_account)
+    }
+
+    pub fn sell_with_intent<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellWithIntent<'info>>,
+        intent: SellIntent,
+    ) -> Result<SellQuote> {
+        sell_with_intent_handler(ctx, intent)
+    }
+
+    pub fn verify_curve(ctx: Context<VerifyCurve>) -> Result<CurveVerification> {
+        verify_curve_handler(ctx)
+    }
+
+    pub fn close_user_position(ctx: Context<CloseUserPosition>) -> Result<()> {
+        close_user_position_handler(ctx)
+    }
+
+    pub fn update_global_config(
+        ctx: Context<UpdateGlobalConfig>,
+        params: UpdateGlobalConfigParams,
     ) -> Result<()> {
-        sell_handler(ctx, token_amount, min_sol_amount)
+        update_global_config_handler(ctx, params)
+    }
+
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        claim_creator_fees_handler(ctx)
+    }
+
+    pub fn migrate_bonding_curve(ctx: Context<MigrateBondingCurve>) -> Result<()> {
+        migrate_bonding_curve_handler(ctx)
+    }
+
+    pub fn transfer_creator(ctx: Context<TransferCreator>, new_creator: Pubkey) -> Result<()> {
+        transfer_creator_handler(ctx, new_creator)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        register_referrer_handler(ctx)
+    }
+
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        claim_referral_fees_handler(ctx)
+    }
+
+    pub fn withdraw_protocol_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawProtocolFees<'info>>,
+    ) -> Result<()> {
+        withdraw_protocol_fees_handler(ctx)
+    }
+
+    pub fn set_fee_split(
+        ctx: Context<SetFeeSplit>,
+        recipients: Vec<FeeSplitRecipient>,
+        dust_recipient_index: u8,
+    ) -> Result<()> {
+        set_fee_split_handler(ctx, recipients, dust_recipient_index)
+    }
+
+    pub fn get_twap(ctx: Context<GetTwap>, window_seconds: i64) -> Result<TwapResult> {
+        get_twap_handler(ctx, window_seconds)
+    }
+
+    pub fn initialize_price_candles(ctx: Context<InitializePriceCandles>) -> Result<()> {
+        initialize_price_candles_handler(ctx)
+    }
+
+    pub fn quote_buy(ctx: Context<Quote>, sol_amount: u64) -> Result<BuyQuote> {
+        quote_buy_handler(ctx, sol_amount)
+    }
+
+    pub fn quote_buy_exact_out(ctx: Context<Quote>, token_amount: u64) -> Result<BuyQuote> {
+        quote_buy_exact_out_handler(ctx, token_amount)
+    }
+
+    pub fn quote_sell(ctx: Context<Quote>, token_amount: u64) -> Result<SellQuote> {
+        quote_sell_handler(ctx, token_amount)
+    }
+
+    pub fn get_curve_state(ctx: Context<Quote>) -> Result<CurveState> {
+        get_curve_state_handler(ctx)
+    }
+
+    pub fn swap_curves<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapCurves<'info>>,
+        token_amount_in: u64,
+        min_token_amount_out: u64,
+        hook_accounts_a: u8,
+    ) -> Result<SwapResult> {
+        swap_curves_handler(ctx, token_amount_in, min_token_amount_out, hook_accounts_a)
+    }
+
+    pub fn place_limit_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlaceLimitOrder<'info>>,
+        params: PlaceLimitOrderParams,
+    ) -> Result<()> {
+        place_limit_order_handler(ctx, params)
+    }
+
+    pub fn cancel_limit_order<'info>(ctx: Context<'_, '_, '_, 'info, CancelLimitOrder<'info>>) -> Result<()> {
+        cancel_limit_order_handler(ctx)
+    }
+
+    pub fn execute_order<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteOrder<'info>>) -> Result<()> {
+        execute_order_handler(ctx)
+    }
+
+    pub fn create_dca_order(ctx: Context<CreateDcaOrder>, params: CreateDcaOrderParams) -> Result<()> {
+        create_dca_order_handler(ctx, params)
+    }
+
+    pub fn execute_dca_fill<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteDcaFill<'info>>) -> Result<()> {
+        execute_dca_fill_handler(ctx)
+    }
+
+    pub fn cancel_dca_order(ctx: Context<CancelDcaOrder>) -> Result<()> {
+        cancel_dca_order_handler(ctx)
     }
 }
@@ -0,0 +1,259 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{BondingCurve, GlobalConfig};
+use crate::utils::{
+    calculate_buy_amount, calculate_buy_cost, calculate_price, calculate_sell_amount, calculate_trade_fees,
+    calculate_trade_fees_for_net_amount, price_change_bps, FeeRates, TradeFees,
+};
+
+/// Outcome of a buy against the curve's current state.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct BuyQuote {
+    pub token_amount: u64,
+    /// Total charged to the buyer, fees included
+    pub sol_amount: u64,
+    /// SOL added to the curve's reserves
+    pub sol_amount_after_fee: u64,
+    /// Unspent input returned to the buyer on a partial fill
+    pub refunded_sol_amount: u64,
+    pub fees: TradeFees,
+    pub price_before: u128,
+    pub price_after: u128,
+    pub price_impact_bps: u64,
+    /// Curve reserves after the trade
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+}
+
+/// Outcome of a sell against the curve's current state.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct SellQuote {
+    pub token_amount: u64,
+    /// SOL taken out of the curve's reserves, fees included
+    pub sol_amount: u64,
+    /// SOL paid to the seller
+    pub sol_amount_after_fee: u64,
+    pub fees: TradeFees,
+    pub price_before: u128,
+    pub price_after: u128,
+    pub price_impact_bps: u64,
+    /// Curve reserves after the trade
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+}
+
+/// Buy fee rates at `now`: the curve's buy fee plus the dynamic fee.
+pub fn buy_fee_rates(
+    bonding_curve: &BondingCurve,
+    global_config: &GlobalConfig,
+    now: i64,
+    referral_share_bps: u16,
+) -> Result<FeeRates> {
+    Ok(FeeRates {
+        protocol_fee_bps: bonding_curve.buy_fee_bps
+            .checked_add(dynamic_fee_bps(bonding_curve, global_config, now))
+            .ok_or(ErrorCode::FeeOverflow)?,
+        creator_fee_bps: bonding_curve.creator_fee_bps,
+        referral_share_bps,
+    })
+}
+
+/// Sell fee rates at `now`: the curve's sell fee or launch tax plus the dynamic fee.
+pub fn sell_fee_rates(
+    bonding_curve: &BondingCurve,
+    global_config: &GlobalConfig,
+    now: i64,
+    referral_share_bps: u16,
+) -> Result<FeeRates> {
+    Ok(FeeRates {
+        protocol_fee_bps: bonding_curve.current_sell_fee_bps(now)
+            .checked_add(dynamic_fee_bps(bonding_curve, global_config, now))
+            .ok_or(ErrorCode::FeeOverflow)?,
+        creator_fee_bps: bonding_curve.creator_fee_bps,
+        referral_share_bps,
+    })
+}
+
+fn dynamic_fee_bps(bonding_curve: &BondingCurve, global_config: &GlobalConfig, now: i64) -> u16 {
+    let dynamic_fee = global_config.dynamic_fee;
+    dynamic_fee.fee_bps(bonding_curve.decayed_volatility(now, dynamic_fee.decay_window_seconds))
+}
+
+/// Spends up to `sol_amount`, filling partially when the curve holds fewer than `remaining_tokens`.
+pub fn quote_buy(
+    bonding_curve: &BondingCurve,
+    fee_rates: &FeeRates,
+    sol_amount: u64,
+    remaining_tokens: u64,
+) -> Result<BuyQuote> {
+    require!(sol_amount > 0, ErrorCode::InvalidAmount);
+    require!(remaining_tokens > 0, ErrorCode::InsufficientReserves);
+
+    let mut fees = calculate_trade_fees(sol_amount, fee_rates)?;
+    let mut sol_amount_after_fee = sol_amount.checked_sub(fees.total()?).ok_or_else(|| {
+        msg!("Fee exceeds trade: sol_amount={} fees={:?}", sol_amount, fees);
+        error!(ErrorCode::FeeOverflow)
+    })?;
+
+    let sol_reserves = bonding_curve.current_sol_reserves()?;
+    let token_reserves = bonding_curve.current_token_reserves()?;
+    let mut token_amount = calculate_buy_amount(sol_amount_after_fee, sol_reserves, token_reserves)?;
+
+    // Partial fill: cap at what the curve still holds and only charge for that
+    if token_amount > remaining_tokens {
+        token_amount = remaining_tokens;
+        sol_amount_after_fee = calculate_buy_cost(token_amount, sol_reserves, token_reserves)?;
+        fees = calculate_trade_fees_for_net_amount(
+            sol_amount_after_fee,
+            fee_rates,
+            sol_amount.saturating_sub(sol_amount_after_fee),
+        )?;
+    }
+
+    build_buy_quote(bonding_curve, sol_amount, sol_amount_after_fee, token_amount, fees)
+}
+
+/// Buys exactly `token_amount`, charging whatever SOL and fees that costs.
+pub fn quote_buy_exact_out(
+    bonding_curve: &BondingCurve,
+    fee_rates: &FeeRates,
+    token_amount: u64,
+    remaining_tokens: u64,
+) -> Result<BuyQuote> {
+    require!(token_amount > 0, ErrorCode::InvalidAmount);
+    require!(token_amount <= remaining_tokens, ErrorCode::InsufficientReserves);
+
+    let sol_amount_after_fee = calculate_buy_cost(
+        token_amount,
+        bonding_curve.current_sol_reserves()?,
+        bonding_curve.current_token_reserves()?,
+    )?;
+    let fees = calculate_trade_fees_for_net_amount(sol_amount_after_fee, fee_rates, u64::MAX)?;
+    let sol_amount = sol_amount_after_fee
+        .checked_add(fees.total()?)
+        .ok_or(ErrorCode::FeeOverflow)?;
+
+    build_buy_quote(bonding_curve, sol_amount, sol_amount_after_fee, token_amount, fees)
+}
+
+fn build_buy_quote(
+    bonding_curve: &BondingCurve,
+    sol_amount: u64,
+    sol_amount_after_fee: u64,
+    token_amount: u64,
+    fees: TradeFees,
+) -> Result<BuyQuote> {
+    let sol_amount_charged = sol_amount_after_fee
+        .checked_add(fees.total()?)
+        .ok_or(ErrorCode::FeeOverflow)?;
+    let refunded_sol_amount = sol_amount.checked_sub(sol_amount_charged).ok_or_else(|| {
+        msg!("Charge exceeds input: sol_amount={} sol_amount_charged={}", sol_amount, sol_amount_charged);
+        error!(ErrorCode::FeeOverflow)
+    })?;
+
+    let virtual_sol_reserves = bonding_curve.current_sol_reserves()?
+        .checked_add(sol_amount_after_fee)
+        .ok_or(ErrorCode::ReserveOverflow)?;
+    let virtual_token_reserves = bonding_curve.current_token_reserves()?
+        .checked_sub(token_amount)
+        .ok_or(ErrorCode::InsufficientReserves)?;
+    let price_before = bonding_curve.spot_price()?;
+    let price_after = calculate_price(virtual_sol_reserves, virtual_token_reserves)?;
+
+    Ok(BuyQuote {
+        token_amount,
+        sol_amount: sol_amount_charged,
+        sol_amount_after_fee,
+        refunded_sol_amount,
+        fees,
+        price_before,
+        price_after,
+        price_impact_bps: price_change_bps(price_before, price_after),
+        virtual_sol_reserves,
+        virtual_token_reserves,
+    })
+}
+
+/// Sells exactly `token_amount` into the curve.
+pub fn quote_sell(bonding_curve: &BondingCurve, fee_rates: &FeeRates, token_amount: u64) -> Result<SellQuote> {
+    require!(token_amount > 0, ErrorCode::InvalidAmount);
+
+    let sol_reserves = bonding_curve.current_sol_reserves()?;
+    let token_reserves = bonding_curve.current_token_reserves()?;
+    let sol_amount = calculate_sell_amount(token_amount, token_reserves, sol_reserves)?;
+
+    let fees = calculate_trade_fees(sol_amount, fee_rates)?;
+    let fee_amount = fees.total()?;
+    let sol_amount_after_fee = sol_amount.checked_sub(fee_amount).ok_or_else(|| {
+        msg!("Fee exceeds trade: sol_amount={} fee_amount={}", sol_amount, fee_amount);
+        error!(ErrorCode::FeeOverflow)
+    })?;
+
+    let virtual_sol_reserves = sol_reserves
+        .checked_sub(sol_amount)
+        .ok_or(ErrorCode::InsufficientReserves)?;
+    let virtual_token_reserves = token_reserves
+        .checked_add(token_amount)
+        .ok_or(ErrorCode::ReserveOverflow)?;
+    let price_before = bonding_curve.spot_price()?;
+    let price_after = calculate_price(virtual_sol_reserves, virtual_token_reserves)?;
+
+    Ok(SellQuote {
+        token_amount,
+        sol_amount,
+        sol_amount_after_fee,
+        fees,
+        price_before,
+        price_after,
+        price_impact_bps: price_change_bps(price_before, price_after),
+        virtual_sol_reserves,
+        virtual_token_reserves,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::DynamicFeeConfig;
+
+    fn tiered_curve() -> BondingCurve {
+        BondingCurve { buy_fee_bps: 50, sell_fee_bps: 150, creator_fee_bps: 20, ..Default::default() }
+    }
+
+    #[test]
+    fn fee_rates_use_the_curve_fee_tier() {
+        let bonding_curve = tiered_curve();
+        let global_config = GlobalConfig::default();
+
+        let buy = buy_fee_rates(&bonding_curve, &global_config, 0, 1_000).unwrap();
+        assert_eq!(buy.protocol_fee_bps, 50);
+        assert_eq!(buy.creator_fee_bps, 20);
+        assert_eq!(buy.referral_share_bps, 1_000);
+
+        let sell = sell_fee_rates(&bonding_curve, &global_config, 0, 1_000).unwrap();
+        assert_eq!(sell.protocol_fee_bps, 150);
+        assert_eq!(sell.creator_fee_bps, 20);
+    }
+
+    #[test]
+    fn fee_rates_add_the_decayed_dynamic_fee() {
+        let bonding_curve = BondingCurve {
+            volatility_accumulator: 4_000,
+            volatility_last_update_timestamp: 1_000,
+            ..tiered_curve()
+        };
+        let global_config = GlobalConfig {
+            dynamic_fee: DynamicFeeConfig {
+                decay_window_seconds: 100,
+                volatility_coefficient_bps: 500,
+                max_dynamic_fee_bps: 150,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(buy_fee_rates(&bonding_curve, &global_config, 1_000, 0).unwrap().protocol_fee_bps, 200);
+        assert_eq!(buy_fee_rates(&bonding_curve, &global_config, 1_050, 0).unwrap().protocol_fee_bps, 150);
+        assert_eq!(sell_fee_rates(&bonding_curve, &global_config, 1_050, 0).unwrap().protocol_fee_bps, 250);
+        assert_eq!(sell_fee_rates(&bonding_curve, &global_config, 1_100, 0).unwrap().protocol_fee_bps, 150);
+    }
+}
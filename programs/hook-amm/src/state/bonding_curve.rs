@@ -1,7 +1,19 @@
 use anchor_lang::prelude::*;
+use crate::constants::{TWAP_OBSERVATION_COUNT, TWAP_OBSERVATION_INTERVAL_SECONDS};
+use crate::errors::ErrorCode;
+use crate::quote::{BuyQuote, SellQuote};
+use crate::state::GlobalConfig;
+use crate::utils::{calculate_price, price_change_bps};
+
+/// Snapshot of the cumulative price, used as the start of a TWAP window.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct PriceObservation {
+    pub timestamp: i64,
+    pub price_cumulative: u128,
+}
 
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct BondingCurve {
     pub mint: Pubkey,
     pub creator: Pubkey,
@@ -12,4 +24,569 @@ pub struct BondingCurve {
     pub token_total_supply: u64,
     pub complete: bool,
     pub index: u64,
-}
\ No newline at end of file
+    /// Buys are rejected before this timestamp
+    pub trading_start_timestamp: i64,
+    /// Seconds after `trading_start_timestamp` during which the per-wallet cap applies
+    pub protected_window_seconds: i64,
+    /// Maximum tokens a wallet may buy during the protected window, 0 for no cap
+    pub max_tokens_per_wallet: u64,
+    /// Fee accrued to the creator on every trade, in basis points
+    pub creator_fee_bps: u16,
+    /// Protocol fees this curve has paid into the protocol fee vault
+    pub protocol_fees_accrued: u64,
+    /// Protocol fees from the tier chosen at creation, in basis points
+    pub buy_fee_bps: u16,
+    pub sell_fee_bps: u16,
+    /// Sell fee at `trading_start_timestamp`, decaying linearly to `sell_fee_bps`
+    pub sell_tax_start_bps: u16,
+    /// Seconds over which the sell tax decays, 0 for no schedule
+    pub sell_tax_duration_seconds: i64,
+    /// Accumulated price movement in basis points, decaying over the dynamic fee window
+    pub volatility_accumulator: u64,
+    pub volatility_last_update_timestamp: i64,
+    /// Spot price before the first trade of `last_trade_slot`
+    pub slot_start_price: u128,
+    pub last_trade_slot: u64,
+    /// Sum of spot price times seconds elapsed, wrapping on overflow
+    pub price_cumulative: u128,
+    pub price_last_update_timestamp: i64,
+    /// Ring buffer of cumulative price snapshots, at least `TWAP_OBSERVATION_INTERVAL_SECONDS` apart
+    pub twap_observations: [PriceObservation; TWAP_OBSERVATION_COUNT],
+    pub twap_observation_index: u8,
+    /// Set once the curve's candles account exists; every trade must then record into it
+    pub price_candles_initialized: bool,
+}
+
+impl BondingCurve {
+    /// Resets volatility and the TWAP accumulator so price history starts at `now`.
+    pub fn start_price_history(&mut self, now: i64) {
+        self.volatility_accumulator = 0;
+        self.volatility_last_update_timestamp = now;
+        self.price_cumulative = 0;
+        self.price_last_update_timestamp = now;
+        self.twap_observations = [PriceObservation::default(); TWAP_OBSERVATION_COUNT];
+        self.twap_observations[0] = PriceObservation {
+            timestamp: now,
+            price_cumulative: 0,
+        };
+        self.twap_observation_index = 0;
+    }
+
+    /// Whether buys at `now` are subject to the per-wallet cap.
+    pub fn is_launch_window_active(&self, now: i64) -> bool {
+        self.max_tokens_per_wallet > 0
+            && now < self.trading_start_timestamp.saturating_add(self.protected_window_seconds)
+    }
+
+    /// Rejects buys that bypass the per-wallet cap while the launch window is active.
+    /// Launch allocations are tracked per wallet and not available on keeper or swap paths.
+    pub fn require_launch_window_closed(&self, now: i64) -> Result<()> {
+        require!(!self.is_launch_window_active(now), ErrorCode::LaunchPurchaseRequired);
+        Ok(())
+    }
+
+    /// Protocol sell fee at `now`, following the decaying sell tax schedule if one is set.
+    pub fn current_sell_fee_bps(&self, now: i64) -> u16 {
+        if self.sell_tax_duration_seconds <= 0 || self.sell_tax_start_bps <= self.sell_fee_bps {
+            return self.sell_fee_bps;
+        }
+
+        let elapsed = now.saturating_sub(self.trading_start_timestamp).max(0);
+        if elapsed >= self.sell_tax_duration_seconds {
+            return self.sell_fee_bps;
+        }
+
+        let excess_bps = (self.sell_tax_start_bps - self.sell_fee_bps) as i128;
+        let remaining = (self.sell_tax_duration_seconds - elapsed) as i128;
+        let decayed_excess_bps = excess_bps * remaining / self.sell_tax_duration_seconds as i128;
+
+        self.sell_fee_bps + decayed_excess_bps as u16
+    }
+
+    /// Volatility accumulator at `now` after linear decay over `decay_window_seconds`.
+    pub fn decayed_volatility(&self, now: i64, decay_window_seconds: i64) -> u64 {
+        if decay_window_seconds <= 0 {
+            return 0;
+        }
+
+        let elapsed = now.saturating_sub(self.volatility_last_update_timestamp).max(0);
+        if elapsed >= decay_window_seconds {
+            return 0;
+        }
+
+        ((self.volatility_accumulator as u128) * ((decay_window_seconds - elapsed) as u128)
+            / (decay_window_seconds as u128)) as u64
+    }
+
+    /// Adds a trade's price movement to the decayed volatility accumulator.
+    pub fn record_price_move(
+        &mut self,
+        now: i64,
+        decay_window_seconds: i64,
+        price_before: u128,
+        price_after: u128,
+    ) {
+        self.volatility_accumulator = self
+            .decayed_volatility(now, decay_window_seconds)
+            .saturating_add(price_change_bps(price_before, price_after));
+        self.volatility_last_update_timestamp = now;
+    }
+
+    /// Rejects a trade whose price move, alone or combined with earlier trades in
+    /// the same slot, exceeds the configured limits. Zero limits are disabled.
+    pub fn enforce_price_impact(
+        &mut self,
+        slot: u64,
+        max_price_impact_bps: u16,
+        max_slot_price_move_bps: u16,
+        price_before: u128,
+        price_after: u128,
+    ) -> Result<()> {
+        let price_impact_bps = price_change_bps(price_before, price_after);
+        if max_price_impact_bps > 0 && price_impact_bps > max_price_impact_bps as u64 {
+            msg!(
+                "Price impact too high: price_before={} price_after={} impact_bps={} max_bps={}",
+                price_before,
+                price_after,
+                price_impact_bps,
+                max_price_impact_bps
+            );
+            return err!(ErrorCode::PriceImpactTooHigh);
+        }
+
+        if self.last_trade_slot != slot {
+            self.last_trade_slot = slot;
+            self.slot_start_price = price_before;
+        }
+
+        let slot_price_move_bps = price_change_bps(self.slot_start_price, price_after);
+        if max_slot_price_move_bps > 0 && slot_price_move_bps > max_slot_price_move_bps as u64 {
+            msg!(
+                "Slot price move too high: slot={} slot_start_price={} price_after={} move_bps={} max_bps={}",
+                slot,
+                self.slot_start_price,
+                price_after,
+                slot_price_move_bps,
+                max_slot_price_move_bps
+            );
+            return err!(ErrorCode::PriceImpactTooHigh);
+        }
+
+        Ok(())
+    }
+
+    /// Adds a filled buy to the reserves and runs the per-trade price bookkeeping.
+    /// Returns the spot price before and after the trade.
+    pub fn apply_buy(
+        &mut self,
+        global_config: &GlobalConfig,
+        quote: &BuyQuote,
+        now: i64,
+        slot: u64,
+    ) -> Result<(u128, u128)> {
+        let price_before = self.spot_price()?;
+        self.update_price_accumulator(now)?;
+
+        // For buy: SOL increases, tokens decrease (but real_token_reserves tracks tokens taken OUT)
+        self.real_sol_reserves = self.real_sol_reserves
+            .checked_add(quote.sol_amount_after_fee)
+            .ok_or_else(|| {
+                msg!(
+                    "Real SOL reserve overflow: real_sol_reserves={} sol_amount_after_fee={}",
+                    self.real_sol_reserves,
+                    quote.sol_amount_after_fee
+                );
+                error!(ErrorCode::ReserveOverflow)
+            })?;
+        self.real_token_reserves = self.real_token_reserves
+            .checked_add(quote.token_amount)
+            .ok_or_else(|| {
+                msg!(
+                    "Real token reserve overflow: real_token_reserves={} token_amount={}",
+                    self.real_token_reserves,
+                    quote.token_amount
+                );
+                error!(ErrorCode::ReserveOverflow)
+            })?;
+        self.protocol_fees_accrued = self.protocol_fees_accrued
+            .checked_add(quote.fees.protocol_fee)
+            .ok_or(ErrorCode::FeeOverflow)?;
+
+        let price_after = self.finish_trade(global_config, now, slot, price_before)?;
+        Ok((price_before, price_after))
+    }
+
+    /// Removes a filled sell from the reserves and runs the per-trade price bookkeeping.
+    /// Returns the spot price before and after the trade.
+    pub fn apply_sell(
+        &mut self,
+        global_config: &GlobalConfig,
+        quote: &SellQuote,
+        now: i64,
+        slot: u64,
+    ) -> Result<(u128, u128)> {
+        let price_before = self.spot_price()?;
+        self.update_price_accumulator(now)?;
+
+        // For sell: SOL decreases, tokens increase (tokens are returned to pool)
+        self.real_sol_reserves = self.real_sol_reserves
+            .checked_sub(quote.sol_amount)
+            .ok_or_else(|| {
+                msg!(
+                    "Real SOL reserve underflow: real_sol_reserves={} sol_amount={}",
+                    self.real_sol_reserves,
+                    quote.sol_amount
+                );
+                error!(ErrorCode::ReserveUnderflow)
+            })?;
+        self.real_token_reserves = self.real_token_reserves
+            .checked_sub(quote.token_amount)
+            .ok_or_else(|| {
+                msg!(
+                    "Real token reserve underflow: real_token_reserves={} token_amount={}",
+                    self.real_token_reserves,
+                    quote.token_amount
+                );
+                error!(ErrorCode::ReserveUnderflow)
+            })?;
+        self.protocol_fees_accrued = self.protocol_fees_accrued
+            .checked_add(quote.fees.protocol_fee)
+            .ok_or(ErrorCode::FeeOverflow)?;
+
+        let price_after = self.finish_trade(global_config, now, slot, price_before)?;
+        Ok((price_before, price_after))
+    }
+
+    fn finish_trade(&mut self, global_config: &GlobalConfig, now: i64, slot: u64, price_before: u128) -> Result<u128> {
+        let price_after = self.spot_price()?;
+        self.record_price_move(now, global_config.dynamic_fee.decay_window_seconds, price_before, price_after);
+        self.enforce_price_impact(
+            slot,
+            global_config.max_price_impact_bps,
+            global_config.max_slot_price_move_bps,
+            price_before,
+            price_after,
+        )?;
+        Ok(price_after)
+    }
+
+    /// Cumulative price extended to `now` at the current spot price.
+    pub fn price_cumulative_at(&self, now: i64) -> Result<u128> {
+        let elapsed = now.saturating_sub(self.price_last_update_timestamp).max(0) as u128;
+        Ok(self.price_cumulative.wrapping_add(self.spot_price()?.wrapping_mul(elapsed)))
+    }
+
+    /// Accumulates the pre-trade spot price up to `now`; call before reserves change.
+    pub fn update_price_accumulator(&mut self, now: i64) -> Result<()> {
+        if now <= self.price_last_update_timestamp {
+            return Ok(());
+        }
+
+        self.price_cumulative = self.price_cumulative_at(now)?;
+        self.price_last_update_timestamp = now;
+
+        let latest = self.twap_observations[self.twap_observation_index as usize];
+        if now.saturating_sub(latest.timestamp) >= TWAP_OBSERVATION_INTERVAL_SECONDS {
+            self.twap_observation_index = ((self.twap_observation_index as usize + 1) % TWAP_OBSERVATION_COUNT) as u8;
+            self.twap_observations[self.twap_observation_index as usize] = PriceObservation {
+                timestamp: now,
+                price_cumulative: self.price_cumulative,
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Newest observation at least `window_seconds` older than `now`.
+    pub fn twap_observation_before(&self, now: i64, window_seconds: i64) -> Option<PriceObservation> {
+        let target = now.saturating_sub(window_seconds);
+        self.twap_observations
+            .iter()
+            .filter(|observation| observation.timestamp > 0 && observation.timestamp <= target)
+            .max_by_key(|observation| observation.timestamp)
+            .copied()
+    }
+
+    /// Spot price in lamports per token unit, scaled by `PRICE_PRECISION`.
+    pub fn spot_price(&self) -> Result<u128> {
+        calculate_price(self.current_sol_reserves()?, self.current_token_reserves()?)
+    }
+
+    /// SOL side of the constant product: virtual plus real SOL reserves.
+    pub fn current_sol_reserves(&self) -> Result<u64> {
+        self.virtual_sol_reserves
+            .checked_add(self.real_sol_reserves)
+            .ok_or_else(|| {
+                msg!(
+                    "SOL reserve overflow: virtual_sol_reserves={} real_sol_reserves={}",
+                    self.virtual_sol_reserves,
+                    self.real_sol_reserves
+                );
+                error!(ErrorCode::ReserveOverflow)
+            })
+    }
+
+    /// Token side of the constant product: virtual reserves plus the supply still held by the curve.
+    pub fn current_token_reserves(&self) -> Result<u64> {
+        let with_supply = self.virtual_token_reserves
+            .checked_add(self.token_total_supply)
+            .ok_or_else(|| {
+                msg!(
+                    "Token reserve overflow: virtual_token_reserves={} token_total_supply={}",
+                    self.virtual_token_reserves,
+                    self.token_total_supply
+                );
+                error!(ErrorCode::ReserveOverflow)
+            })?;
+
+        with_supply
+            .checked_sub(self.real_token_reserves)
+            .ok_or_else(|| {
+                msg!(
+                    "Token reserve underflow: virtual_token_reserves={} token_total_supply={} real_token_reserves={}",
+                    self.virtual_token_reserves,
+                    self.token_total_supply,
+                    self.real_token_reserves
+                );
+                error!(ErrorCode::ReserveUnderflow)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve() -> BondingCurve {
+        BondingCurve {
+            virtual_token_reserves: 2_000_000,
+            virtual_sol_reserves: 1_000_000_000,
+            token_total_supply: 1_000_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unallocated_buys_wait_for_the_launch_window_to_close() {
+        let bonding_curve = BondingCurve {
+            trading_start_timestamp: 1_000,
+            protected_window_seconds: 60,
+            max_tokens_per_wallet: 500,
+            ..curve()
+        };
+
+        assert_eq!(
+            bonding_curve.require_launch_window_closed(1_059).unwrap_err(),
+            ErrorCode::LaunchPurchaseRequired.into()
+        );
+        assert!(bonding_curve.require_launch_window_closed(1_060).is_ok());
+
+        let uncapped = BondingCurve { max_tokens_per_wallet: 0, ..bonding_curve };
+        assert!(uncapped.require_launch_window_closed(1_000).is_ok());
+    }
+
+    fn taxed_curve() -> BondingCurve {
+        BondingCurve {
+            trading_start_timestamp: 1_000,
+            sell_fee_bps: 100,
+            sell_tax_start_bps: 1_100,
+            sell_tax_duration_seconds: 600,
+            ..curve()
+        }
+    }
+
+    #[test]
+    fn sell_tax_decays_linearly_over_its_window() {
+        let bonding_curve = taxed_curve();
+
+        assert_eq!(bonding_curve.current_sell_fee_bps(900), 1_100);
+        assert_eq!(bonding_curve.current_sell_fee_bps(1_000), 1_100);
+        assert_eq!(bonding_curve.current_sell_fee_bps(1_300), 600);
+        assert_eq!(bonding_curve.current_sell_fee_bps(1_599), 101);
+        assert_eq!(bonding_curve.current_sell_fee_bps(1_600), 100);
+        assert_eq!(bonding_curve.current_sell_fee_bps(10_000), 100);
+    }
+
+    #[test]
+    fn sell_tax_without_schedule_is_the_sell_fee() {
+        let no_duration = BondingCurve { sell_tax_duration_seconds: 0, ..taxed_curve() };
+        assert_eq!(no_duration.current_sell_fee_bps(1_000), 100);
+
+        let no_excess = BondingCurve { sell_tax_start_bps: 100, ..taxed_curve() };
+        assert_eq!(no_excess.current_sell_fee_bps(1_000), 100);
+    }
+
+    #[test]
+    fn volatility_decays_linearly_to_zero() {
+        let bonding_curve = BondingCurve {
+            volatility_accumulator: 1_000,
+            volatility_last_update_timestamp: 1_000,
+            ..curve()
+        };
+
+        assert_eq!(bonding_curve.decayed_volatility(900, 100), 1_000);
+        assert_eq!(bonding_curve.decayed_volatility(1_000, 100), 1_000);
+        assert_eq!(bonding_curve.decayed_volatility(1_025, 100), 750);
+        assert_eq!(bonding_curve.decayed_volatility(1_099, 100), 10);
+        assert_eq!(bonding_curve.decayed_volatility(1_100, 100), 0);
+        assert_eq!(bonding_curve.decayed_volatility(1_000, 0), 0);
+    }
+
+    #[test]
+    fn price_moves_add_to_decayed_volatility() {
+        let mut bonding_curve = BondingCurve {
+            volatility_accumulator: 1_000,
+            volatility_last_update_timestamp: 1_000,
+            ..curve()
+        };
+
+        bonding_curve.record_price_move(1_050, 100, 1_000, 1_100);
+        assert_eq!(bonding_curve.volatility_accumulator, 1_500);
+        assert_eq!(bonding_curve.volatility_last_update_timestamp, 1_050);
+
+        bonding_curve.record_price_move(1_050, 100, 1_100, 990);
+        assert_eq!(bonding_curve.volatility_accumulator, 2_500);
+
+        bonding_curve.record_price_move(2_000, 100, 1_000, 1_000);
+        assert_eq!(bonding_curve.volatility_accumulator, 0);
+        assert_eq!(bonding_curve.volatility_last_update_timestamp, 2_000);
+    }
+
+    #[test]
+    fn price_accumulator_integrates_spot_price_over_time() {
+        let mut bonding_curve = curve();
+        bonding_curve.start_price_history(1_000);
+        let price = bonding_curve.spot_price().unwrap();
+
+        bonding_curve.update_price_accumulator(1_100).unwrap();
+        assert_eq!(bonding_curve.price_cumulative, price * 100);
+        assert_eq!(bonding_curve.twap_observation_index, 0);
+
+        bonding_curve.update_price_accumulator(1_300).unwrap();
+        assert_eq!(bonding_curve.price_cumulative, price * 300);
+        assert_eq!(bonding_curve.twap_observation_index, 1);
+        assert_eq!(bonding_curve.twap_observations[1].timestamp, 1_300);
+        assert_eq!(bonding_curve.twap_observations[1].price_cumulative, price * 300);
+
+        bonding_curve.update_price_accumulator(1_200).unwrap();
+        assert_eq!(bonding_curve.price_cumulative, price * 300);
+        assert_eq!(bonding_curve.price_cumulative_at(1_400).unwrap(), price * 400);
+        assert_eq!(bonding_curve.price_last_update_timestamp, 1_300);
+    }
+
+    #[test]
+    fn price_accumulator_wraps_on_overflow() {
+        let mut bonding_curve = curve();
+        bonding_curve.start_price_history(1_000);
+        bonding_curve.price_cumulative = u128::MAX;
+        let price = bonding_curve.spot_price().unwrap();
+
+        bonding_curve.update_price_accumulator(1_001).unwrap();
+        assert_eq!(bonding_curve.price_cumulative, price - 1);
+    }
+
+    #[test]
+    fn observation_ring_overwrites_the_oldest_entry() {
+        let mut bonding_curve = curve();
+        bonding_curve.start_price_history(1_000);
+
+        let interval = TWAP_OBSERVATION_INTERVAL_SECONDS;
+        let last = 1_000 + interval * TWAP_OBSERVATION_COUNT as i64;
+        for step in 1..=TWAP_OBSERVATION_COUNT as i64 {
+            bonding_curve.update_price_accumulator(1_000 + interval * step).unwrap();
+        }
+
+        assert_eq!(bonding_curve.twap_observation_index, 0);
+        assert_eq!(bonding_curve.twap_observations[0].timestamp, last);
+        assert_eq!(bonding_curve.twap_observations[1].timestamp, 1_000 + interval);
+    }
+
+    #[test]
+    fn observation_before_picks_the_newest_old_enough_snapshot() {
+        let mut bonding_curve = curve();
+        bonding_curve.start_price_history(1_000);
+        bonding_curve.update_price_accumulator(1_300).unwrap();
+        bonding_curve.update_price_accumulator(1_600).unwrap();
+
+        assert_eq!(bonding_curve.twap_observation_before(1_900, 300).unwrap().timestamp, 1_600);
+        assert_eq!(bonding_curve.twap_observation_before(1_900, 500).unwrap().timestamp, 1_300);
+        assert_eq!(bonding_curve.twap_observation_before(1_900, 900).unwrap().timestamp, 1_000);
+    }
+
+    #[test]
+    fn observation_before_is_none_for_a_window_older_than_history() {
+        let mut bonding_curve = curve();
+        bonding_curve.start_price_history(1_000);
+        bonding_curve.update_price_accumulator(1_300).unwrap();
+
+        assert!(bonding_curve.twap_observation_before(1_300, 301).is_none());
+        assert!(bonding_curve.twap_observation_before(1_300, 10_000).is_none());
+    }
+
+    #[test]
+    fn apply_buy_and_sell_update_reserves() {
+        let mut bonding_curve = curve();
+        let global_config = GlobalConfig::default();
+
+        let buy = BuyQuote { token_amount: 1_000, sol_amount_after_fee: 600_000, ..Default::default() };
+        let (price_before, price_after) = bonding_curve.apply_buy(&global_config, &buy, 0, 0).unwrap();
+        assert!(price_after > price_before);
+        assert_eq!(bonding_curve.real_sol_reserves, 600_000);
+        assert_eq!(bonding_curve.real_token_reserves, 1_000);
+
+        let sell = SellQuote { token_amount: 1_000, sol_amount: 600_000, ..Default::default() };
+        let (price_before, price_after) = bonding_curve.apply_sell(&global_config, &sell, 0, 0).unwrap();
+        assert!(price_after < price_before);
+        assert_eq!(bonding_curve.real_sol_reserves, 0);
+        assert_eq!(bonding_curve.real_token_reserves, 0);
+    }
+
+    #[test]
+    fn apply_buy_rejects_sol_reserve_overflow() {
+        let mut bonding_curve = BondingCurve { virtual_sol_reserves: 1, real_sol_reserves: u64::MAX - 1, ..curve() };
+        let buy = BuyQuote { token_amount: 1, sol_amount_after_fee: 1, ..Default::default() };
+
+        assert_eq!(
+            bonding_curve.apply_buy(&GlobalConfig::default(), &buy, 0, 0).unwrap_err(),
+            ErrorCode::ReserveOverflow.into()
+        );
+    }
+
+    #[test]
+    fn apply_buy_rejects_token_reserve_overflow() {
+        let mut bonding_curve = BondingCurve {
+            virtual_token_reserves: 0,
+            token_total_supply: u64::MAX,
+            real_token_reserves: u64::MAX - 1,
+            ..curve()
+        };
+        let buy = BuyQuote { token_amount: 2, sol_amount_after_fee: 1, ..Default::default() };
+
+        assert_eq!(
+            bonding_curve.apply_buy(&GlobalConfig::default(), &buy, 0, 0).unwrap_err(),
+            ErrorCode::ReserveOverflow.into()
+        );
+    }
+
+    #[test]
+    fn apply_sell_rejects_sol_reserve_underflow() {
+        let mut bonding_curve = BondingCurve { real_sol_reserves: 100, real_token_reserves: 1_000, ..curve() };
+        let sell = SellQuote { token_amount: 1, sol_amount: 101, ..Default::default() };
+
+        assert_eq!(
+            bonding_curve.apply_sell(&GlobalConfig::default(), &sell, 0, 0).unwrap_err(),
+            ErrorCode::ReserveUnderflow.into()
+        );
+    }
+
+    #[test]
+    fn apply_sell_rejects_token_reserve_underflow() {
+        let mut bonding_curve = BondingCurve { real_sol_reserves: 100, real_token_reserves: 1_000, ..curve() };
+        let sell = SellQuote { token_amount: 1_001, sol_amount: 1, ..Default::default() };
+
+        assert_eq!(
+            bonding_curve.apply_sell(&GlobalConfig::default(), &sell, 0, 0).unwrap_err(),
+            ErrorCode::ReserveUnderflow.into()
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Holds creator fees accrued by a curve until the creator claims them.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorFeeVault {
+    pub bonding_curve: Pubkey,
+    pub total_accrued: u64,
+    pub total_claimed: u64,
+}
+
+impl CreatorFeeVault {
+    pub fn claimable(&self) -> u64 {
+        self.total_accrued.saturating_sub(self.total_claimed)
+    }
+
+    pub fn accrue(&mut self, amount: u64) -> Result<()> {
+        self.total_accrued = self.total_accrued.checked_add(amount).ok_or(ErrorCode::FeeOverflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Points at a curve from a PDA keyed by its global index or by its launching
+/// creator's running count. The by-creator PDA keeps the launching creator's key
+/// after `transfer_creator`; read the current creator from the bonding curve.
+#[account]
+#[derive(InitSpace)]
+pub struct CurveRegistryEntry {
+    pub bonding_curve: Pubkey,
+    pub mint: Pubkey,
+    pub index: u64,
+}
+
+/// Number of curves a creator has launched; the next curve's creator index.
+/// Launches are counted against the launching creator and are not moved by `transfer_creator`.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorCurveCounter {
+    pub creator: Pubkey,
+    pub curve_count: u64,
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Recurring buy funded by SOL escrowed in this account, filled by keepers at most once per interval.
+#[account]
+#[derive(InitSpace)]
+pub struct DcaOrder {
+    pub owner: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub order_id: u64,
+    /// Lamports spent on each fill, fees included
+    pub amount_per_fill: u64,
+    pub fills_remaining: u64,
+    pub interval_seconds: i64,
+    pub next_fill_timestamp: i64,
+    /// Highest average price a fill may execute at, scaled by `PRICE_PRECISION`
+    pub max_price: u128,
+    /// Lamports paid to the keeper on each fill
+    pub keeper_reward: u64,
+}
+
+impl DcaOrder {
+    /// Lamports to escrow for `fills` fills including keeper rewards.
+    pub fn escrow_amount(amount_per_fill: u64, keeper_reward: u64, fills: u64) -> Result<u64> {
+        amount_per_fill
+            .checked_add(keeper_reward)
+            .and_then(|per_fill| per_fill.checked_mul(fills))
+            .ok_or_else(|| error!(ErrorCode::Overflow))
+    }
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use crate::constants::{FEE_DENOMINATOR, MAX_FEE_SPLIT_RECIPIENTS};
+use crate::errors::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct FeeSplitRecipient {
+    pub recipient: Pubkey,
+    pub weight_bps: u16,
+}
+
+/// Weighted recipients of protocol fees, applied when fees are withdrawn.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeSplit {
+    #[max_len(MAX_FEE_SPLIT_RECIPIENTS)]
+    pub recipients: Vec<FeeSplitRecipient>,
+    /// Index into `recipients` that receives rounding dust
+    pub dust_recipient_index: u8,
+}
+
+impl FeeSplit {
+    /// Checks a new recipient list: distinct recipients with nonzero weights summing
+    /// to `FEE_DENOMINATOR`, and a dust recipient in range. An empty list is valid.
+    pub fn validate(recipients: &[FeeSplitRecipient], dust_recipient_index: u8) -> Result<()> {
+        require!(recipients.len() <= MAX_FEE_SPLIT_RECIPIENTS, ErrorCode::InvalidFeeSplit);
+        if recipients.is_empty() {
+            return Ok(());
+        }
+
+        require!((dust_recipient_index as usize) < recipients.len(), ErrorCode::InvalidFeeSplit);
+
+        let mut total_weight_bps: u64 = 0;
+        for (i, entry) in recipients.iter().enumerate() {
+            require!(entry.weight_bps > 0, ErrorCode::InvalidFeeSplit);
+            require!(
+                recipients[..i].iter().all(|other| other.recipient != entry.recipient),
+                ErrorCode::InvalidFeeSplit
+            );
+            total_weight_bps += entry.weight_bps as u64;
+        }
+        require!(total_weight_bps == FEE_DENOMINATOR, ErrorCode::InvalidFeeSplit);
+
+        Ok(())
+    }
+
+    /// Each recipient's share of `amount`, in `recipients` order, with rounding dust
+    /// added to the dust recipient.
+    pub fn split(&self, amount: u64) -> Result<Vec<u64>> {
+        let mut shares = self.recipients
+            .iter()
+            .map(|entry| {
+                u64::try_from((amount as u128) * (entry.weight_bps as u128) / (FEE_DENOMINATOR as u128))
+                    .map_err(|_| error!(ErrorCode::Overflow))
+            })
+            .collect::<Result<Vec<u64>>>()?;
+
+        let distributed = shares.iter().try_fold(0u64, |sum, share| sum.checked_add(*share))
+            .ok_or(ErrorCode::Overflow)?;
+        let dust = amount.checked_sub(distributed).ok_or(ErrorCode::Overflow)?;
+        let dust_share = shares
+            .get_mut(self.dust_recipient_index as usize)
+            .ok_or(ErrorCode::InvalidFeeSplit)?;
+        *dust_share = dust_share.checked_add(dust).ok_or(ErrorCode::Overflow)?;
+
+        Ok(shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fee_split(weights: &[u16], dust_recipient_index: u8) -> FeeSplit {
+        FeeSplit {
+            recipients: weights
+                .iter()
+                .map(|weight_bps| FeeSplitRecipient { recipient: Pubkey::new_unique(), weight_bps: *weight_bps })
+                .collect(),
+            dust_recipient_index,
+        }
+    }
+
+    #[test]
+    fn weights_must_sum_to_denominator() {
+        assert!(FeeSplit::validate(&fee_split(&[5_000, 3_000, 2_000], 2).recipients, 2).is_ok());
+        assert!(FeeSplit::validate(&[], 0).is_ok());
+
+        for (weights, dust_recipient_index) in [
+            (&[5_000, 4_999][..], 0),
+            (&[5_000, 5_001][..], 0),
+            (&[10_000, 0][..], 0),
+            (&[5_000, 5_000][..], 2),
+            (&[1_250; MAX_FEE_SPLIT_RECIPIENTS + 1][..], 0),
+        ] {
+            assert_eq!(
+                FeeSplit::validate(&fee_split(weights, dust_recipient_index).recipients, dust_recipient_index)
+                    .unwrap_err(),
+                ErrorCode::InvalidFeeSplit.into()
+            );
+        }
+    }
+
+    #[test]
+    fn duplicate_recipients_are_rejected() {
+        let mut split = fee_split(&[5_000, 5_000], 0);
+        split.recipients[1].recipient = split.recipients[0].recipient;
+
+        assert_eq!(
+            FeeSplit::validate(&split.recipients, 0).unwrap_err(),
+            ErrorCode::InvalidFeeSplit.into()
+        );
+    }
+
+    #[test]
+    fn shares_follow_weights_and_sum_to_amount() {
+        let shares = fee_split(&[5_000, 3_000, 2_000], 0).split(1_000_000).unwrap();
+
+        assert_eq!(shares, vec![500_000, 300_000, 200_000]);
+        assert_eq!(shares.iter().sum::<u64>(), 1_000_000);
+    }
+
+    #[test]
+    fn rounding_dust_goes_to_dust_recipient() {
+        // Each third rounds down to 33, leaving 1 lamport of dust
+        let weights = [3_334, 3_333, 3_333];
+
+        assert_eq!(fee_split(&weights, 0).split(100).unwrap(), vec![34, 33, 33]);
+        assert_eq!(fee_split(&weights, 2).split(100).unwrap(), vec![33, 33, 34]);
+    }
+
+    #[test]
+    fn small_amounts_go_entirely_to_dust_recipient() {
+        assert_eq!(fee_split(&[5_000, 5_000], 1).split(1).unwrap(), vec![0, 1]);
+    }
+
+    #[test]
+    fn out_of_range_dust_recipient_is_rejected() {
+        assert_eq!(
+            fee_split(&[5_000, 5_000], 2).split(101).unwrap_err(),
+            ErrorCode::InvalidFeeSplit.into()
+        );
+    }
+}
@@ -1,9 +1,97 @@
 use anchor_lang::prelude::*;
+use crate::constants::{
+    DEFAULT_MAX_CREATOR_FEE_BPS, DEFAULT_REFERRAL_SHARE_BPS, FEE_BASIS_POINTS, FEE_DENOMINATOR, MAX_FEE_TIERS,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct FeeTier {
+    pub buy_fee_bps: u16,
+    pub sell_fee_bps: u16,
+}
+
+/// Protocol fee surcharge that grows with a curve's recent price movement.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct DynamicFeeConfig {
+    /// Seconds for accumulated volatility to decay linearly to zero
+    pub decay_window_seconds: i64,
+    /// Fee charged per basis point of accumulated volatility, in basis points
+    pub volatility_coefficient_bps: u16,
+    /// Cap on the dynamic fee; 0 disables it
+    pub max_dynamic_fee_bps: u16,
+}
+
+impl DynamicFeeConfig {
+    pub fn fee_bps(&self, volatility_bps: u64) -> u16 {
+        if self.max_dynamic_fee_bps == 0 {
+            return 0;
+        }
+
+        let fee_bps = (volatility_bps as u128) * (self.volatility_coefficient_bps as u128)
+            / (FEE_DENOMINATOR as u128);
+        fee_bps.min(self.max_dynamic_fee_bps as u128) as u16
+    }
+}
 
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct GlobalConfig {
     pub authority: Pubkey,
     pub fee_recipient: Pubkey,
     pub total_curves: u64,
-}
\ No newline at end of file
+    /// Bounds on the creator fee a curve may choose at creation
+    pub min_creator_fee_bps: u16,
+    pub max_creator_fee_bps: u16,
+    /// Share of the protocol fee paid to a trade's referrer, in basis points of that fee
+    pub referral_share_bps: u16,
+    /// When set, protocol fee withdrawals are split according to the fee split account
+    pub fee_split_enabled: bool,
+    /// Protocol fee tiers a curve may choose from at creation
+    #[max_len(MAX_FEE_TIERS)]
+    pub fee_tiers: Vec<FeeTier>,
+    pub dynamic_fee: DynamicFeeConfig,
+    /// Maximum spot price change a single trade may cause, in basis points; 0 disables
+    pub max_price_impact_bps: u16,
+    /// Maximum spot price change within one slot, in basis points; 0 disables
+    pub max_slot_price_move_bps: u16,
+}
+
+impl GlobalConfig {
+    /// Fee and risk settings a new or migrated config starts with.
+    pub fn set_default_parameters(&mut self) {
+        self.min_creator_fee_bps = 0;
+        self.max_creator_fee_bps = DEFAULT_MAX_CREATOR_FEE_BPS;
+        self.referral_share_bps = DEFAULT_REFERRAL_SHARE_BPS;
+        self.fee_split_enabled = false;
+        self.fee_tiers = vec![FeeTier {
+            buy_fee_bps: FEE_BASIS_POINTS,
+            sell_fee_bps: FEE_BASIS_POINTS,
+        }];
+        self.dynamic_fee = DynamicFeeConfig::default();
+        self.max_price_impact_bps = 0;
+        self.max_slot_price_move_bps = 0;
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dynamic_fee(max_dynamic_fee_bps: u16) -> DynamicFeeConfig {
+        DynamicFeeConfig { decay_window_seconds: 300, volatility_coefficient_bps: 500, max_dynamic_fee_bps }
+    }
+
+    #[test]
+    fn dynamic_fee_scales_with_volatility_up_to_the_cap() {
+        let config = dynamic_fee(200);
+
+        assert_eq!(config.fee_bps(0), 0);
+        assert_eq!(config.fee_bps(1_000), 50);
+        assert_eq!(config.fee_bps(4_000), 200);
+        assert_eq!(config.fee_bps(100_000), 200);
+        assert_eq!(config.fee_bps(u64::MAX), 200);
+    }
+
+    #[test]
+    fn zero_cap_disables_the_dynamic_fee() {
+        assert_eq!(dynamic_fee(0).fee_bps(100_000), 0);
+    }
+}
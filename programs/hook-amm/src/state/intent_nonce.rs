@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+/// Next nonce a user's signed intent must carry; bumped on every executed intent.
+#[account]
+#[derive(InitSpace)]
+pub struct IntentNonce {
+    pub user: Pubkey,
+    pub nonce: u64,
+}
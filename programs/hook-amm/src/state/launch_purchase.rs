@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+/// Tokens a wallet has received from a curve's buys, used to enforce the launch window cap.
+#[account]
+#[derive(InitSpace)]
+pub struct LaunchPurchase {
+    pub bonding_curve: Pubkey,
+    pub user: Pubkey,
+    pub tokens_purchased: u64,
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+/// Resting order filled by a keeper once the curve's spot price crosses `target_price`.
+/// Buy orders escrow SOL in this account; sell orders escrow tokens in a token account it owns.
+#[account]
+#[derive(InitSpace)]
+pub struct LimitOrder {
+    pub owner: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub order_id: u64,
+    pub is_buy: bool,
+    /// Lamports to spend for a buy, tokens to sell for a sell
+    pub amount_in: u64,
+    /// Spot price in lamports per token unit, scaled by `PRICE_PRECISION`
+    pub target_price: u128,
+    pub min_amount_out: u64,
+    pub expiry_timestamp: i64,
+    /// Lamports paid to whoever executes the order
+    pub keeper_reward: u64,
+}
+
+impl LimitOrder {
+    /// Buys fill at or below the target price, sells at or above it.
+    pub fn is_triggered(&self, spot_price: u128) -> bool {
+        if self.is_buy {
+            spot_price <= self.target_price
+        } else {
+            spot_price >= self.target_price
+        }
+    }
+}
@@ -1,5 +1,27 @@
 pub mod global_config;
 pub mod bonding_curve;
+pub mod launch_purchase;
+pub mod user_position;
+pub mod creator_fee_vault;
+pub mod referrer;
+pub mod protocol_fee_vault;
+pub mod fee_split;
+pub mod price_candles;
+pub mod limit_order;
+pub mod dca_order;
+pub mod intent_nonce;
+pub mod curve_registry;
 
 pub use global_config::*;
-pub use bonding_curve::*;
\ No newline at end of file
+pub use bonding_curve::*;
+pub use launch_purchase::*;
+pub use user_position::*;
+pub use creator_fee_vault::*;
+pub use referrer::*;
+pub use protocol_fee_vault::*;
+pub use fee_split::*;
+pub use price_candles::*;
+pub use limit_order::*;
+pub use dca_order::*;
+pub use intent_nonce::*;
+pub use curve_registry::*;
\ No newline at end of file
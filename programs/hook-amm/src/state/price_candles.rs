@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use crate::constants::{
+    CANDLE_HOUR_COUNT, CANDLE_HOUR_SECONDS, CANDLE_MINUTE_COUNT, CANDLE_MINUTE_SECONDS,
+};
+
+/// One OHLCV bucket. Prices are spot prices scaled by `PRICE_PRECISION`, saturated to u64.
+#[zero_copy]
+#[derive(Default, Debug)]
+pub struct Candle {
+    pub open_timestamp: i64,
+    pub open: u64,
+    pub high: u64,
+    pub low: u64,
+    pub close: u64,
+    pub sol_volume: u64,
+    pub token_volume: u64,
+    pub trade_count: u64,
+}
+
+/// Per-curve ring buffers of minute and hour candles, updated by `buy` and `sell`.
+#[account(zero_copy)]
+pub struct PriceCandles {
+    pub bonding_curve: Pubkey,
+    /// Index of the most recent minute candle
+    pub minute_head: u32,
+    /// Index of the most recent hour candle
+    pub hour_head: u32,
+    pub minute_candles: [Candle; CANDLE_MINUTE_COUNT],
+    pub hour_candles: [Candle; CANDLE_HOUR_COUNT],
+}
+
+impl PriceCandles {
+    pub const SIZE: usize = 32 + 4 + 4 + (CANDLE_MINUTE_COUNT + CANDLE_HOUR_COUNT) * std::mem::size_of::<Candle>();
+
+    pub fn record_trade(
+        &mut self,
+        now: i64,
+        price_before: u128,
+        price_after: u128,
+        sol_amount: u64,
+        token_amount: u64,
+    ) {
+        let trade = CandleTrade {
+            now,
+            price_before: u64::try_from(price_before).unwrap_or(u64::MAX),
+            price_after: u64::try_from(price_after).unwrap_or(u64::MAX),
+            sol_amount,
+            token_amount,
+        };
+
+        record_candle(&mut self.minute_candles, &mut self.minute_head, CANDLE_MINUTE_SECONDS, &trade);
+        record_candle(&mut self.hour_candles, &mut self.hour_head, CANDLE_HOUR_SECONDS, &trade);
+    }
+}
+
+struct CandleTrade {
+    now: i64,
+    price_before: u64,
+    price_after: u64,
+    sol_amount: u64,
+    token_amount: u64,
+}
+
+/// Opens a new candle at `price_before` when the trade falls past the head's interval, then folds the trade in.
+fn record_candle(candles: &mut [Candle], head: &mut u32, interval_seconds: i64, trade: &CandleTrade) {
+    let open_timestamp = trade.now - trade.now.rem_euclid(interval_seconds);
+
+    if open_timestamp > candles[*head as usize].open_timestamp {
+        if candles[*head as usize].trade_count > 0 {
+            *head = (*head + 1) % candles.len() as u32;
+        }
+        candles[*head as usize] = Candle {
+            open_timestamp,
+            open: trade.price_before,
+            high: trade.price_before,
+            low: trade.price_before,
+            close: trade.price_before,
+            ..Candle::default()
+        };
+    }
+
+    let candle = &mut candles[*head as usize];
+    candle.high = candle.high.max(trade.price_after);
+    candle.low = candle.low.min(trade.price_after);
+    candle.close = trade.price_after;
+    candle.sol_volume = candle.sol_volume.saturating_add(trade.sol_amount);
+    candle.token_volume = candle.token_volume.saturating_add(trade.token_amount);
+    candle.trade_count = candle.trade_count.saturating_add(1);
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Holds protocol fees from every curve until the authority withdraws them.
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolFeeVault {
+    pub total_accrued: u64,
+    pub total_withdrawn: u64,
+}
+
+impl ProtocolFeeVault {
+    pub fn withdrawable(&self) -> u64 {
+        self.total_accrued.saturating_sub(self.total_withdrawn)
+    }
+
+    pub fn accrue(&mut self, amount: u64) -> Result<()> {
+        self.total_accrued = self.total_accrued.checked_add(amount).ok_or(ErrorCode::FeeOverflow)?;
+        Ok(())
+    }
+}
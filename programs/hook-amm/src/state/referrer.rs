@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Referral fees owed to a frontend, held here until claimed.
+#[account]
+#[derive(InitSpace)]
+pub struct Referrer {
+    pub authority: Pubkey,
+    pub total_accrued: u64,
+    pub total_claimed: u64,
+    /// SOL volume of trades routed through this referrer
+    pub referred_volume: u64,
+    pub trade_count: u64,
+}
+
+impl Referrer {
+    pub fn claimable(&self) -> u64 {
+        self.total_accrued.saturating_sub(self.total_claimed)
+    }
+
+    pub fn record_trade(&mut self, referral_fee: u64, sol_volume: u64) -> Result<()> {
+        self.total_accrued = self.total_accrued.checked_add(referral_fee).ok_or(ErrorCode::FeeOverflow)?;
+        self.referred_volume = self.referred_volume.checked_add(sol_volume).ok_or(ErrorCode::Overflow)?;
+        self.trade_count = self.trade_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
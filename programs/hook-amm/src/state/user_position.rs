@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use crate::constants::PRICE_PRECISION;
+use crate::errors::ErrorCode;
+
+/// A user's trading history on a single curve.
+#[account]
+#[derive(InitSpace, Default)]
+pub struct UserPosition {
+    pub bonding_curve: Pubkey,
+    pub user: Pubkey,
+    pub tokens_bought: u64,
+    pub tokens_sold: u64,
+    /// Tokens bought through the curve and not yet sold
+    pub tokens_held: u64,
+    /// SOL paid for buys, fees included
+    pub sol_spent: u64,
+    /// SOL received from sells, fees excluded
+    pub sol_received: u64,
+    pub fees_paid: u64,
+    /// SOL cost of the tokens still held, using average cost
+    pub cost_basis: u64,
+    /// Average entry price in lamports per token unit, scaled by `PRICE_PRECISION`
+    pub average_entry_price: u128,
+    pub realized_pnl: i64,
+}
+
+impl UserPosition {
+    pub fn record_buy(&mut self, token_amount: u64, sol_amount: u64, fee_amount: u64) -> Result<()> {
+        self.tokens_bought = self.tokens_bought.checked_add(token_amount).ok_or(ErrorCode::Overflow)?;
+        self.tokens_held = self.tokens_held.checked_add(token_amount).ok_or(ErrorCode::Overflow)?;
+        self.sol_spent = self.sol_spent.checked_add(sol_amount).ok_or(ErrorCode::Overflow)?;
+        self.fees_paid = self.fees_paid.checked_add(fee_amount).ok_or(ErrorCode::Overflow)?;
+        self.cost_basis = self.cost_basis.checked_add(sol_amount).ok_or(ErrorCode::Overflow)?;
+        self.update_average_entry_price();
+        Ok(())
+    }
+
+    /// Realizes PnL against the average cost of the tokens sold. Tokens sold beyond
+    /// what was bought through the curve carry no cost basis.
+    pub fn record_sell(&mut self, token_amount: u64, sol_amount: u64, fee_amount: u64) -> Result<()> {
+        let tokens_held = self.tokens_held;
+        let tokens_with_basis = token_amount.min(tokens_held);
+        let cost_of_sold = if tokens_held == 0 {
+            0
+        } else {
+            let cost = (self.cost_basis as u128)
+                .checked_mul(tokens_with_basis as u128)
+                .ok_or(ErrorCode::Overflow)?
+                / tokens_held as u128;
+            u64::try_from(cost).map_err(|_| ErrorCode::Overflow)?
+        };
+
+        let pnl = (sol_amount as i128) - (cost_of_sold as i128);
+        self.realized_pnl = i64::try_from((self.realized_pnl as i128) + pnl).map_err(|_| ErrorCode::Overflow)?;
+
+        self.tokens_sold = self.tokens_sold.checked_add(token_amount).ok_or(ErrorCode::Overflow)?;
+        self.tokens_held = tokens_held - tokens_with_basis;
+        self.sol_received = self.sol_received.checked_add(sol_amount).ok_or(ErrorCode::Overflow)?;
+        self.fees_paid = self.fees_paid.checked_add(fee_amount).ok_or(ErrorCode::Overflow)?;
+        self.cost_basis = self.cost_basis.checked_sub(cost_of_sold).ok_or(ErrorCode::Overflow)?;
+        self.update_average_entry_price();
+        Ok(())
+    }
+
+    fn update_average_entry_price(&mut self) {
+        // A u64 cost basis scaled by PRICE_PRECISION always fits in u128
+        self.average_entry_price = if self.tokens_held == 0 {
+            0
+        } else {
+            (self.cost_basis as u128) * PRICE_PRECISION / (self.tokens_held as u128)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buys_and_sells_track_average_cost_and_realized_pnl() {
+        let mut position = UserPosition::default();
+
+        position.record_buy(1_000, 1_000_000, 10_000).unwrap();
+        position.record_buy(1_000, 3_000_000, 30_000).unwrap();
+        assert_eq!(position.tokens_held, 2_000);
+        assert_eq!(position.cost_basis, 4_000_000);
+        assert_eq!(position.average_entry_price, 2_000 * PRICE_PRECISION);
+
+        // Selling a quarter realizes PnL against a quarter of the cost basis
+        position.record_sell(500, 1_500_000, 15_000).unwrap();
+        assert_eq!(position.tokens_held, 1_500);
+        assert_eq!(position.cost_basis, 3_000_000);
+        assert_eq!(position.average_entry_price, 2_000 * PRICE_PRECISION);
+        assert_eq!(position.realized_pnl, 500_000);
+
+        position.record_sell(1_500, 2_000_000, 20_000).unwrap();
+        assert_eq!(position.tokens_held, 0);
+        assert_eq!(position.cost_basis, 0);
+        assert_eq!(position.average_entry_price, 0);
+        assert_eq!(position.realized_pnl, -500_000);
+        assert_eq!(position.tokens_bought, 2_000);
+        assert_eq!(position.tokens_sold, 2_000);
+        assert_eq!(position.sol_spent, 4_000_000);
+        assert_eq!(position.sol_received, 3_500_000);
+        assert_eq!(position.fees_paid, 75_000);
+    }
+
+    #[test]
+    fn tokens_sold_without_cost_basis_realize_full_proceeds() {
+        let mut position = UserPosition::default();
+
+        position.record_buy(100, 1_000, 0).unwrap();
+        position.record_sell(300, 5_000, 0).unwrap();
+        assert_eq!(position.tokens_held, 0);
+        assert_eq!(position.realized_pnl, 4_000);
+    }
+
+    #[test]
+    fn average_entry_price_above_u64_is_stored() {
+        let mut position = UserPosition::default();
+
+        position.record_buy(1, u64::MAX, 0).unwrap();
+        assert_eq!(position.average_entry_price, (u64::MAX as u128) * PRICE_PRECISION);
+    }
+}
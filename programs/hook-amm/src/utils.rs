@@ -1,7 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::token_interface::{Mint, TokenInterface};
 use anchor_spl::token_2022;
+use crate::constants::{FEE_DENOMINATOR, PRICE_PRECISION};
 use crate::errors::ErrorCode;
+use crate::events::TradeEvent;
+use crate::quote::{BuyQuote, SellQuote};
+use crate::state::{
+    BondingCurve, CreatorFeeVault, GlobalConfig, PriceCandles, ProtocolFeeVault, Referrer, UserPosition,
+};
 
 pub fn calculate_buy_amount(
     sol_amount: u64,
@@ -30,6 +36,38 @@ pub fn calculate_buy_amount(
     Ok(tokens_out)
 }
 
+/// SOL (after fee) required to take exactly `token_amount` out of the curve, rounded up.
+pub fn calculate_buy_cost(
+    token_amount: u64,
+    sol_reserves: u64,
+    token_reserves: u64,
+) -> Result<u64> {
+    let token_amount_u128 = token_amount as u128;
+    let sol_reserves_u128 = sol_reserves as u128;
+    let token_reserves_u128 = token_reserves as u128;
+    
+    // new_token_reserves = token_reserves - token_amount, must stay positive
+    let new_token_reserves_u128 = token_reserves_u128.checked_sub(token_amount_u128).ok_or(ErrorCode::InsufficientReserves)?;
+    require!(new_token_reserves_u128 > 0, ErrorCode::InsufficientReserves);
+    
+    // k = sol_reserves * token_reserves (using u128 to prevent overflow)
+    let k = sol_reserves_u128.checked_mul(token_reserves_u128).ok_or(ErrorCode::Overflow)?;
+    
+    // new_sol_reserves = ceil(k / new_token_reserves)
+    let new_sol_reserves_u128 = k
+        .checked_add(new_token_reserves_u128 - 1)
+        .ok_or(ErrorCode::Overflow)?
+        / new_token_reserves_u128;
+    
+    // sol_in = new_sol_reserves - sol_reserves
+    let sol_in_u128 = new_sol_reserves_u128.checked_sub(sol_reserves_u128).ok_or(ErrorCode::Overflow)?;
+    
+    // Convert back to u64, checking for overflow
+    let sol_in = u64::try_from(sol_in_u128).map_err(|_| ErrorCode::Overflow)?;
+    
+    Ok(sol_in)
+}
+
 pub fn calculate_sell_amount(
     token_amount: u64,
     token_reserves: u64,
@@ -57,9 +95,405 @@ pub fn calculate_sell_amount(
     Ok(sol_out)
 }
 
+/// Price in lamports per token unit, scaled by `PRICE_PRECISION`.
+pub fn calculate_price(sol_reserves: u64, token_reserves: u64) -> Result<u128> {
+    require!(token_reserves > 0, ErrorCode::InsufficientReserves);
+
+    (sol_reserves as u128)
+        .checked_mul(PRICE_PRECISION)
+        .map(|scaled| scaled / token_reserves as u128)
+        .ok_or_else(|| error!(ErrorCode::Overflow))
+}
+
+/// Absolute change from `price_before` to `price_after`, in basis points of `price_before`.
+pub fn price_change_bps(price_before: u128, price_after: u128) -> u64 {
+    if price_before == 0 {
+        return 0;
+    }
+
+    let change = price_before.abs_diff(price_after).saturating_mul(FEE_DENOMINATOR as u128) / price_before;
+    u64::try_from(change).unwrap_or(u64::MAX)
+}
+
+pub fn calculate_fee(amount: u64, fee_basis_points: u16) -> Result<u64> {
+    let fee = amount
+        .checked_mul(fee_basis_points as u64)
+        .ok_or_else(|| {
+            msg!("Fee overflow: amount={} fee_basis_points={}", amount, fee_basis_points);
+            error!(ErrorCode::FeeOverflow)
+        })?
+        / FEE_DENOMINATOR;
+
+    Ok(fee)
+}
+
+/// Fee to add on top of `net_amount` so that the fee is `fee_basis_points` of the gross, rounded up.
+pub fn calculate_fee_for_net_amount(net_amount: u64, fee_basis_points: u16) -> Result<u64> {
+    let remaining_basis_points = FEE_DENOMINATOR
+        .checked_sub(fee_basis_points as u64)
+        .filter(|bps| *bps > 0)
+        .ok_or_else(|| {
+            msg!("Invalid fee: fee_basis_points={}", fee_basis_points);
+            error!(ErrorCode::FeeOverflow)
+        })?;
+
+    let fee = (net_amount as u128)
+        .checked_mul(fee_basis_points as u128)
+        .and_then(|v| v.checked_add(remaining_basis_points as u128 - 1))
+        .map(|v| v / remaining_basis_points as u128)
+        .and_then(|v| u64::try_from(v).ok())
+        .ok_or_else(|| {
+            msg!("Fee overflow: net_amount={} fee_basis_points={}", net_amount, fee_basis_points);
+            error!(ErrorCode::FeeOverflow)
+        })?;
+
+    Ok(fee)
+}
+
+/// Fee rates applied to a single trade.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FeeRates {
+    pub protocol_fee_bps: u16,
+    pub creator_fee_bps: u16,
+    /// Share of the protocol fee paid to the referrer, in basis points of that fee
+    pub referral_share_bps: u16,
+}
+
+impl FeeRates {
+    pub fn total_fee_bps(&self) -> Result<u16> {
+        self.protocol_fee_bps
+            .checked_add(self.creator_fee_bps)
+            .ok_or_else(|| {
+                msg!(
+                    "Fee overflow: protocol_fee_bps={} creator_fee_bps={}",
+                    self.protocol_fee_bps,
+                    self.creator_fee_bps
+                );
+                error!(ErrorCode::FeeOverflow)
+            })
+    }
+}
+
+/// Fees charged on a single trade, by destination.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct TradeFees {
+    /// Protocol fee net of the referral share
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub referral_fee: u64,
+}
+
+impl TradeFees {
+    fn new(protocol_fee: u64, creator_fee: u64, referral_share_bps: u16) -> Result<Self> {
+        let referral_fee = calculate_fee(protocol_fee, referral_share_bps)?;
+
+        Ok(Self {
+            protocol_fee: protocol_fee - referral_fee,
+            creator_fee,
+            referral_fee,
+        })
+    }
+
+    pub fn total(&self) -> Result<u64> {
+        self.protocol_fee
+            .checked_add(self.creator_fee)
+            .and_then(|fee| fee.checked_add(self.referral_fee))
+            .ok_or_else(|| {
+                msg!(
+                    "Fee overflow: protocol_fee={} creator_fee={} referral_fee={}",
+                    self.protocol_fee,
+                    self.creator_fee,
+                    self.referral_fee
+                );
+                error!(ErrorCode::FeeOverflow)
+            })
+    }
+}
+
+/// Fees taken out of a gross `amount`.
+pub fn calculate_trade_fees(amount: u64, rates: &FeeRates) -> Result<TradeFees> {
+    TradeFees::new(
+        calculate_fee(amount, rates.protocol_fee_bps)?,
+        calculate_fee(amount, rates.creator_fee_bps)?,
+        rates.referral_share_bps,
+    )
+}
+
+/// Fees to add on top of `net_amount`, never exceeding `max_total_fee`.
+pub fn calculate_trade_fees_for_net_amount(
+    net_amount: u64,
+    rates: &FeeRates,
+    max_total_fee: u64,
+) -> Result<TradeFees> {
+    let total_fee_bps = rates.total_fee_bps()?;
+    if total_fee_bps == 0 {
+        return Ok(TradeFees::default());
+    }
+
+    let total_fee = calculate_fee_for_net_amount(net_amount, total_fee_bps)?.min(max_total_fee);
+    let creator_fee = u64::try_from(
+        (total_fee as u128) * (rates.creator_fee_bps as u128) / (total_fee_bps as u128)
+    ).map_err(|_| ErrorCode::FeeOverflow)?;
+
+    TradeFees::new(total_fee - creator_fee, creator_fee, rates.referral_share_bps)
+}
+
+/// Expected lamport balance of a curve account: rent-exempt minimum plus real SOL reserves.
+pub fn expected_curve_lamports(bonding_curve: &Account<BondingCurve>) -> Result<u64> {
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(bonding_curve.to_account_info().data_len());
+
+    rent_exempt_minimum
+        .checked_add(bonding_curve.real_sol_reserves)
+        .ok_or_else(|| {
+            msg!(
+                "Expected lamports overflow: rent_exempt_minimum={} real_sol_reserves={}",
+                rent_exempt_minimum,
+                bonding_curve.real_sol_reserves
+            );
+            error!(ErrorCode::ReserveOverflow)
+        })
+}
+
+pub fn assert_curve_solvent(bonding_curve: &Account<BondingCurve>) -> Result<()> {
+    let expected_lamports = expected_curve_lamports(bonding_curve)?;
+    let actual_lamports = bonding_curve.to_account_info().lamports();
+
+    if actual_lamports < expected_lamports {
+        msg!(
+            "Curve insolvent: lamports={} expected={} real_sol_reserves={}",
+            actual_lamports,
+            expected_lamports,
+            bonding_curve.real_sol_reserves
+        );
+        return err!(ErrorCode::CurveInsolvent);
+    }
+
+    Ok(())
+}
+
+/// System transfer from a signer, skipped when `amount` is zero.
+pub fn transfer_sol<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+        from.key,
+        to.key,
+        amount,
+    );
+    anchor_lang::solana_program::program::invoke(
+        &transfer_ix,
+        &[from.clone(), to.clone(), system_program.clone()],
+    )?;
+
+    Ok(())
+}
+
+/// Grows a program-owned account to `new_len`, with `payer` covering the added rent.
+pub fn realloc_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let added_rent = rent
+        .minimum_balance(new_len)
+        .saturating_sub(rent.minimum_balance(account.data_len()));
+    transfer_sol(payer, account, system_program, added_rent)?;
+    account.realloc(new_len, true)?;
+
+    Ok(())
+}
+
+/// Moves lamports out of a program-owned account without a CPI.
+pub fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let from_lamports = from.lamports();
+    **from.try_borrow_mut_lamports()? = from_lamports
+        .checked_sub(amount)
+        .ok_or_else(|| {
+            msg!("Lamport underflow: account={} lamports={} amount={}", from.key, from_lamports, amount);
+            error!(ErrorCode::InsufficientBalance)
+        })?;
+    let to_lamports = to.lamports();
+    **to.try_borrow_mut_lamports()? = to_lamports
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    Ok(())
+}
+
+/// Where a buy's SOL comes from.
+pub enum BuyFunding<'a, 'info> {
+    /// A signer paying through the system program
+    Payer {
+        payer: &'a AccountInfo<'info>,
+        system_program: &'a AccountInfo<'info>,
+    },
+    /// A program-owned escrow, debited directly
+    Escrow(&'a AccountInfo<'info>),
+}
+
+impl<'info> BuyFunding<'_, 'info> {
+    fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        match self {
+            Self::Payer { payer, system_program } => transfer_sol(payer, to, system_program, amount),
+            Self::Escrow(escrow) => transfer_lamports(escrow, to, amount),
+        }
+    }
+}
+
+/// Accounts and context a trade settles into, shared by every buy and sell path.
+pub struct TradeSettlement<'a, 'info> {
+    pub mint: Pubkey,
+    pub global_config: &'a GlobalConfig,
+    pub bonding_curve: &'a mut Account<'info, BondingCurve>,
+    pub protocol_fee_vault: &'a mut Account<'info, ProtocolFeeVault>,
+    pub creator_fee_vault: &'a mut Account<'info, CreatorFeeVault>,
+    pub referrer: Option<&'a mut Account<'info, Referrer>>,
+    pub price_candles: Option<&'a AccountLoader<'info, PriceCandles>>,
+    /// Position of the token holder: the buy's recipient, or the seller
+    pub user_position: Option<&'a mut Account<'info, UserPosition>>,
+    /// Trader reported in the `TradeEvent`
+    pub user: Pubkey,
+    /// Owner of the tokens bought, or receiver of the SOL from a sell
+    pub recipient: Pubkey,
+    pub now: i64,
+    pub slot: u64,
+}
+
+impl TradeSettlement<'_, '_> {
+    /// Candles are required once they exist so they never miss a trade.
+    fn require_price_candles(&self) -> Result<()> {
+        require!(
+            self.price_candles.is_some() || !self.bonding_curve.price_candles_initialized,
+            ErrorCode::PriceCandlesRequired
+        );
+        Ok(())
+    }
+}
+
+/// Applies a buy to the curve, then moves its SOL and fees out of `funding`, accrues
+/// the vaults and referrer, checks solvency, records candles and the position, and
+/// emits the `TradeEvent`. The caller delivers the tokens.
+pub fn settle_buy<'info>(
+    trade: TradeSettlement<'_, 'info>,
+    quote: &BuyQuote,
+    funding: BuyFunding<'_, 'info>,
+) -> Result<()> {
+    trade.require_price_candles()?;
+    let (price_before, price_after) = trade.bonding_curve.apply_buy(trade.global_config, quote, trade.now, trade.slot)?;
+
+    funding.pay(&trade.bonding_curve.to_account_info(), quote.sol_amount_after_fee)?;
+
+    funding.pay(&trade.protocol_fee_vault.to_account_info(), quote.fees.protocol_fee)?;
+    trade.protocol_fee_vault.accrue(quote.fees.protocol_fee)?;
+
+    funding.pay(&trade.creator_fee_vault.to_account_info(), quote.fees.creator_fee)?;
+    trade.creator_fee_vault.accrue(quote.fees.creator_fee)?;
+
+    if let Some(referrer) = trade.referrer {
+        funding.pay(&referrer.to_account_info(), quote.fees.referral_fee)?;
+        referrer.record_trade(quote.fees.referral_fee, quote.sol_amount)?;
+    }
+
+    assert_curve_solvent(trade.bonding_curve)?;
+
+    if let Some(price_candles) = trade.price_candles {
+        price_candles.load_mut()?.record_trade(trade.now, price_before, price_after, quote.sol_amount, quote.token_amount);
+    }
+
+    if let Some(user_position) = trade.user_position {
+        user_position.bonding_curve = trade.bonding_curve.key();
+        user_position.user = trade.recipient;
+        user_position.record_buy(quote.token_amount, quote.sol_amount, quote.fees.total()?)?;
+    }
+
+    emit!(TradeEvent {
+        mint: trade.mint,
+        user: trade.user,
+        recipient: trade.recipient,
+        sol_amount: quote.sol_amount,
+        token_amount: quote.token_amount,
+        is_buy: true,
+        refunded_sol_amount: quote.refunded_sol_amount,
+        creator_fee: quote.fees.creator_fee,
+        referral_fee: quote.fees.referral_fee,
+        virtual_sol_reserves: trade.bonding_curve.current_sol_reserves()?,
+        virtual_token_reserves: trade.bonding_curve.current_token_reserves()?,
+    });
+
+    Ok(())
+}
+
+/// Applies a sell to the curve, then moves its fees out of the curve, accrues the
+/// vaults and referrer, checks solvency, records candles and the position, and emits
+/// the `TradeEvent`. The caller takes in the tokens and pays the proceeds out of the
+/// curve first; `sol_amount_received` is the part of them reported as the seller's.
+pub fn settle_sell<'info>(
+    trade: TradeSettlement<'_, 'info>,
+    quote: &SellQuote,
+    sol_amount_received: u64,
+) -> Result<()> {
+    trade.require_price_candles()?;
+    let (price_before, price_after) = trade.bonding_curve.apply_sell(trade.global_config, quote, trade.now, trade.slot)?;
+
+    let bonding_curve_info = trade.bonding_curve.to_account_info();
+    transfer_lamports(&bonding_curve_info, &trade.protocol_fee_vault.to_account_info(), quote.fees.protocol_fee)?;
+    trade.protocol_fee_vault.accrue(quote.fees.protocol_fee)?;
+
+    transfer_lamports(&bonding_curve_info, &trade.creator_fee_vault.to_account_info(), quote.fees.creator_fee)?;
+    trade.creator_fee_vault.accrue(quote.fees.creator_fee)?;
+
+    if let Some(referrer) = trade.referrer {
+        transfer_lamports(&bonding_curve_info, &referrer.to_account_info(), quote.fees.referral_fee)?;
+        referrer.record_trade(quote.fees.referral_fee, quote.sol_amount)?;
+    }
+
+    // The curve must still cover its rent-exempt minimum plus the reserves it reports
+    assert_curve_solvent(trade.bonding_curve)?;
+
+    if let Some(price_candles) = trade.price_candles {
+        price_candles.load_mut()?.record_trade(trade.now, price_before, price_after, quote.sol_amount, quote.token_amount);
+    }
+
+    if let Some(user_position) = trade.user_position {
+        user_position.bonding_curve = trade.bonding_curve.key();
+        user_position.user = trade.user;
+        user_position.record_sell(quote.token_amount, quote.sol_amount_after_fee, quote.fees.total()?)?;
+    }
+
+    emit!(TradeEvent {
+        mint: trade.mint,
+        user: trade.user,
+        recipient: trade.recipient,
+        sol_amount: sol_amount_received,
+        token_amount: quote.token_amount,
+        is_buy: false,
+        refunded_sol_amount: 0,
+        creator_fee: quote.fees.creator_fee,
+        referral_fee: quote.fees.referral_fee,
+        virtual_sol_reserves: trade.bonding_curve.current_sol_reserves()?,
+        virtual_token_reserves: trade.bonding_curve.current_token_reserves()?,
+    });
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn perform_token_transfer<'info>(
-    from: &InterfaceAccount<'info, TokenAccount>,
-    to: &InterfaceAccount<'info, TokenAccount>,
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
     authority: &AccountInfo<'info>,
     token_program: &Interface<'info, TokenInterface>,
     mint: &InterfaceAccount<'info, Mint>,
@@ -137,4 +571,63 @@ pub fn perform_token_transfer<'info>(
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::entrypoint::SUCCESS;
+    use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+
+    struct RentStubs;
+
+    impl SyscallStubs for RentStubs {
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = Rent::default() };
+            SUCCESS
+        }
+    }
+
+    /// Runs `assert_curve_solvent` on a curve whose balance `lamports` derives from its rent-exempt minimum.
+    fn check_solvency(real_sol_reserves: u64, lamports: impl FnOnce(u64) -> u64) -> Result<()> {
+        set_syscall_stubs(Box::new(RentStubs));
+
+        let mut data = Vec::new();
+        BondingCurve { real_sol_reserves, ..Default::default() }.try_serialize(&mut data)?;
+        data.resize(8 + BondingCurve::INIT_SPACE, 0);
+        let mut lamports = lamports(Rent::default().minimum_balance(data.len()));
+        let key = Pubkey::new_unique();
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+
+        assert_curve_solvent(&Account::<BondingCurve>::try_from(&info)?)
+    }
+
+    #[test]
+    fn solvent_curve_covers_rent_and_reserves() {
+        assert!(check_solvency(1_000, |rent| rent + 1_000).is_ok());
+        assert!(check_solvency(1_000, |rent| rent + 5_000).is_ok());
+    }
+
+    #[test]
+    fn curve_short_of_reserves_is_insolvent() {
+        assert_eq!(
+            check_solvency(1_000, |rent| rent + 999).unwrap_err(),
+            ErrorCode::CurveInsolvent.into()
+        );
+    }
+
+    #[test]
+    fn curve_short_of_rent_is_insolvent() {
+        assert_eq!(
+            check_solvency(0, |rent| rent - 1).unwrap_err(),
+            ErrorCode::CurveInsolvent.into()
+        );
+    }
+
+    #[test]
+    fn expected_lamports_overflow_is_rejected() {
+        assert_eq!(
+            check_solvency(u64::MAX, |_| u64::MAX).unwrap_err(),
+            ErrorCode::ReserveOverflow.into()
+        );
+    }
+}
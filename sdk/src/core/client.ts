@@ -9,7 +9,7 @@ import {
 } from '@solana/spl-token';
 import BN from 'bn.js';
 
-import { PROGRAM_ID } from './constants';
+import { PROGRAM_ID, FEE_DENOMINATOR } from './constants';
 import { 
   GlobalConfig, 
   BondingCurve, 
@@ -26,10 +26,15 @@ import {
   getGlobalConfigPDA, 
   getBondingCurvePDA, 
   getCurveTokenAccountPDA,
+  getProtocolFeeVaultPDA,
+  getCreatorFeeVaultPDA,
+  getPriceCandlesPDA,
   calculateBuyAmount,
   calculateSellAmount,
   calculateTokenPrice,
   calculatePriceImpact,
+  calculateSellFeeBps,
+  calculateDynamicFeeBps,
   getTokenProgramId,
   getMintInfo,
   getTransferHookAccounts,
@@ -111,6 +116,7 @@ export class HookAmmClient {
     options?: SendTransactionOptions
   ): Promise<string> {
     const [globalConfig] = getGlobalConfigPDA();
+    const [protocolFeeVault] = getProtocolFeeVaultPDA();
     const signerPubkey = signer ? getSignerPublicKey(signer) : this.wallet.publicKey;
 
     // Ensure the signer is the authority (as required by program)
@@ -123,6 +129,7 @@ export class HookAmmClient {
         .initializeGlobalConfig()
         .accounts({
           globalConfig,
+          protocolFeeVault,
           authority: signerPubkey,
           feeRecipient,
           systemProgram: SystemProgram.programId,
@@ -137,6 +144,7 @@ export class HookAmmClient {
         .initializeGlobalConfig()
         .accounts({
           globalConfig,
+          protocolFeeVault,
           authority: signerPubkey,
           feeRecipient,
           systemProgram: SystemProgram.programId,
@@ -243,8 +251,11 @@ export class HookAmmClient {
       tokenProgramId
     );
 
-    // Get global config to access fee recipient
-    const globalConfigData = await this.getGlobalConfig();
+    const [protocolFeeVault] = getProtocolFeeVaultPDA();
+    const [creatorFeeVault] = getCreatorFeeVaultPDA(params.bondingCurve);
+    const priceCandles = bondingCurveData.priceCandlesInitialized
+      ? getPriceCandlesPDA(params.bondingCurve)[0]
+      : null;
 
     const instructions = [];
 
@@ -283,7 +294,9 @@ export class HookAmmClient {
       user: signerPubkey,
       mint: bondingCurveData.mint,
       globalConfig,
-      feeRecipient: globalConfigData.feeRecipient,
+      protocolFeeVault,
+      creatorFeeVault,
+      priceCandles,
       tokenProgram: tokenProgramId,
       associatedTokenProgram: new PublicKey('ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL'),
       systemProgram: SystemProgram.programId,
@@ -342,8 +355,11 @@ export class HookAmmClient {
       tokenProgramId
     );
 
-    // Get global config to access fee recipient
-    const globalConfigData = await this.getGlobalConfig();
+    const [protocolFeeVault] = getProtocolFeeVaultPDA();
+    const [creatorFeeVault] = getCreatorFeeVaultPDA(params.bondingCurve);
+    const priceCandles = bondingCurveData.priceCandlesInitialized
+      ? getPriceCandlesPDA(params.bondingCurve)[0]
+      : null;
 
     // Get transfer hook accounts for Token-2022
     const transferHookAccounts = await getTransferHookAccounts(
@@ -365,7 +381,9 @@ export class HookAmmClient {
       user: signerPubkey,
       mint: bondingCurveData.mint,
       globalConfig,
-      feeRecipient: globalConfigData.feeRecipient,
+      protocolFeeVault,
+      creatorFeeVault,
+      priceCandles,
       tokenProgram: tokenProgramId,
       associatedTokenProgram: new PublicKey('ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL'),
       systemProgram: SystemProgram.programId,
@@ -374,7 +392,7 @@ export class HookAmmClient {
 
     if (signer && isWalletAdapter(signer)) {
       const instruction = await this.program.methods
-        .sell(params.tokenAmount, params.minSolAmount)
+        .sell(params.tokenAmount, params.minSolAmount, params.closeTokenAccount ?? false)
         .accounts(sellAccounts)
         .remainingAccounts(allHookAccounts)
         .instruction();
@@ -384,7 +402,7 @@ export class HookAmmClient {
     } else {
       const keypairSigner = signer as Keypair || (this.wallet as any).payer;
       return await this.program.methods
-        .sell(params.tokenAmount, params.minSolAmount)
+        .sell(params.tokenAmount, params.minSolAmount, params.closeTokenAccount ?? false)
         .accounts(sellAccounts)
         .remainingAccounts(allHookAccounts)
         .signers([keypairSigner])
@@ -397,10 +415,12 @@ export class HookAmmClient {
    */
   async getBuyQuote(bondingCurve: PublicKey, solAmount: BN): Promise<PriceQuote> {
     const curve = await this.getBondingCurve(bondingCurve);
+    const globalConfig = await this.getGlobalConfig();
     
     // Apply fee calculation (matches program logic exactly)
-    const FEE_BASIS_POINTS = 100; // 1%
-    const feeAmount = solAmount.mul(new BN(FEE_BASIS_POINTS)).div(new BN(10000));
+    const buyFeeBps = curve.buyFeeBps + calculateDynamicFeeBps(globalConfig.dynamicFee, curve);
+    const feeAmount = solAmount.mul(new BN(buyFeeBps)).div(new BN(FEE_DENOMINATOR))
+      .add(solAmount.mul(new BN(curve.creatorFeeBps)).div(new BN(FEE_DENOMINATOR)));
     const solAmountAfterFee = solAmount.sub(feeAmount);
     
     // Calculate using program's exact reserve formula
@@ -456,9 +476,11 @@ export class HookAmmClient {
       curve.realSolReserves
     );
 
-    // Apply fee (fee is taken from SOL amount in sell)
-    const FEE_BASIS_POINTS = 100; // 1%
-    const feeAmount = solAmount.mul(new BN(FEE_BASIS_POINTS)).div(new BN(10000));
+    // Apply fee (fee is taken from SOL amount in sell, including any launch sell tax)
+    const globalConfig = await this.getGlobalConfig();
+    const sellFeeBps = calculateSellFeeBps(curve) + calculateDynamicFeeBps(globalConfig.dynamicFee, curve);
+    const feeAmount = solAmount.mul(new BN(sellFeeBps)).div(new BN(FEE_DENOMINATOR))
+      .add(solAmount.mul(new BN(curve.creatorFeeBps)).div(new BN(FEE_DENOMINATOR)));
     const solAmountAfterFee = solAmount.sub(feeAmount);
 
     const pricePerToken = calculateTokenPrice(
@@ -6,6 +6,9 @@ export const SEEDS = {
   GLOBAL_CONFIG: 'global_config',
   BONDING_CURVE: 'bonding_curve',
   CURVE_TOKEN_ACCOUNT: 'curve_token_account',
+  PROTOCOL_FEE_VAULT: 'protocol_fee_vault',
+  CREATOR_FEE_VAULT: 'creator_fee_vault',
+  PRICE_CANDLES: 'price_candles',
 } as const;
 
 export const FEE_BASIS_POINTS = 100; // 1%
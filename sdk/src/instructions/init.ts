@@ -1,6 +1,6 @@
 import { PublicKey, TransactionInstruction, SystemProgram } from '@solana/web3.js';
 import { Program } from '@coral-xyz/anchor';
-import { getGlobalConfigPDA } from '../utils/pda';
+import { getGlobalConfigPDA, getProtocolFeeVaultPDA } from '../utils/pda';
 
 export async function createInitializeGlobalConfigInstruction(
   program: Program,
@@ -8,11 +8,13 @@ export async function createInitializeGlobalConfigInstruction(
   feeRecipient: PublicKey
 ): Promise<TransactionInstruction> {
   const [globalConfig] = getGlobalConfigPDA();
+  const [protocolFeeVault] = getProtocolFeeVaultPDA();
 
   return await program.methods
     .initializeGlobalConfig()
     .accounts({
       globalConfig,
+      protocolFeeVault,
       authority,
       feeRecipient,
       systemProgram: SystemProgram.programId,
@@ -1,16 +1,20 @@
 import { PublicKey, TransactionInstruction, SystemProgram } from '@solana/web3.js';
 import { Program } from '@coral-xyz/anchor';
-import { getAssociatedTokenAddress } from '@solana/spl-token';
+import { getAssociatedTokenAddress, ASSOCIATED_TOKEN_PROGRAM_ID } from '@solana/spl-token';
 import BN from 'bn.js';
 
-import { getGlobalConfigPDA, getCurveTokenAccountPDA } from '../utils/pda';
+import {
+  getGlobalConfigPDA,
+  getCurveTokenAccountPDA,
+  getProtocolFeeVaultPDA,
+  getCreatorFeeVaultPDA,
+} from '../utils/pda';
 
 export async function createBuyInstruction(
   program: Program,
   bondingCurve: PublicKey,
   tokenMint: PublicKey,
   user: PublicKey,
-  feeRecipient: PublicKey,
   tokenProgramId: PublicKey,
   solAmount: BN,
   minTokenAmount: BN,
@@ -18,6 +22,8 @@ export async function createBuyInstruction(
 ): Promise<TransactionInstruction> {
   const [globalConfig] = getGlobalConfigPDA();
   const [curveTokenAccount] = getCurveTokenAccountPDA(bondingCurve, tokenMint);
+  const [protocolFeeVault] = getProtocolFeeVaultPDA();
+  const [creatorFeeVault] = getCreatorFeeVaultPDA(bondingCurve);
   
   const userTokenAccount = await getAssociatedTokenAddress(
     tokenMint,
@@ -35,8 +41,10 @@ export async function createBuyInstruction(
       curveTokenAccount,
       userTokenAccount,
       user,
-      feeRecipient,
+      protocolFeeVault,
+      creatorFeeVault,
       tokenProgram: tokenProgramId,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
     })
     .remainingAccounts(hookAccounts.map(account => ({ 
@@ -52,14 +60,16 @@ export async function createSellInstruction(
   bondingCurve: PublicKey,
   tokenMint: PublicKey,
   user: PublicKey,
-  feeRecipient: PublicKey,
   tokenProgramId: PublicKey,
   tokenAmount: BN,
   minSolAmount: BN,
-  hookAccounts: PublicKey[] = []
+  hookAccounts: PublicKey[] = [],
+  closeTokenAccount = false
 ): Promise<TransactionInstruction> {
   const [globalConfig] = getGlobalConfigPDA();
   const [curveTokenAccount] = getCurveTokenAccountPDA(bondingCurve, tokenMint);
+  const [protocolFeeVault] = getProtocolFeeVaultPDA();
+  const [creatorFeeVault] = getCreatorFeeVaultPDA(bondingCurve);
   
   const userTokenAccount = await getAssociatedTokenAddress(
     tokenMint,
@@ -69,7 +79,7 @@ export async function createSellInstruction(
   );
 
   return await program.methods
-    .sell(tokenAmount, minSolAmount)
+    .sell(tokenAmount, minSolAmount, closeTokenAccount)
     .accounts({
       globalConfig,
       bondingCurve,
@@ -77,8 +87,10 @@ export async function createSellInstruction(
       curveTokenAccount,
       userTokenAccount,
       user,
-      feeRecipient,
+      protocolFeeVault,
+      creatorFeeVault,
       tokenProgram: tokenProgramId,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
     })
     .remainingAccounts(hookAccounts.map(account => ({ 
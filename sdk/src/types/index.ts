@@ -8,6 +8,13 @@ export interface GlobalConfig {
   authority: PublicKey;
   feeRecipient: PublicKey;
   totalCurves: BN;
+  dynamicFee: DynamicFeeConfig;
+}
+
+export interface DynamicFeeConfig {
+  decayWindowSeconds: BN;
+  volatilityCoefficientBps: number;
+  maxDynamicFeeBps: number;
 }
 
 export interface BondingCurve {
@@ -20,19 +27,36 @@ export interface BondingCurve {
   tokenTotalSupply: BN;
   complete: boolean;
   index: BN;
+  tradingStartTimestamp: BN;
+  protectedWindowSeconds: BN;
+  maxTokensPerWallet: BN;
+  creatorFeeBps: number;
+  protocolFeesAccrued: BN;
+  buyFeeBps: number;
+  sellFeeBps: number;
+  sellTaxStartBps: number;
+  sellTaxDurationSeconds: BN;
+  volatilityAccumulator: BN;
+  volatilityLastUpdateTimestamp: BN;
+  /** Trades must pass the curve's price candles account once this is set */
+  priceCandlesInitialized: boolean;
 }
 
 export interface TradeEvent {
   mint: PublicKey;
+  user: PublicKey;
+  /** Owner of the tokens bought, or receiver of the SOL from a sell */
+  recipient: PublicKey;
   solAmount: BN;
   tokenAmount: BN;
   isBuy: boolean;
-  user: PublicKey;
-  timestamp: BN;
+  /** Part of a buy's SOL input left with the buyer after a partial fill */
+  refundedSolAmount: BN;
+  creatorFee: BN;
+  referralFee: BN;
+  /** Curve reserves after the trade */
   virtualSolReserves: BN;
   virtualTokenReserves: BN;
-  realSolReserves: BN;
-  realTokenReserves: BN;
 }
 
 export interface CreateBondingCurveParams {
@@ -52,6 +76,8 @@ export interface SellParams {
   bondingCurve: PublicKey;
   tokenAmount: BN;
   minSolAmount: BN;
+  /** Close the token account and reclaim its rent; fails unless the sell empties it */
+  closeTokenAccount?: boolean;
 }
 
 export interface SwapResult {
@@ -75,4 +75,72 @@ export function calculateTokenPrice(
   // Price = SOL reserves / Token reserves
   // Return price in SOL per token with 9 decimal precision
   return currentSolReserves.mul(new BN(10).pow(new BN(9))).div(currentTokenReserves).toNumber() / 1e9;
-}
\ No newline at end of file
+}
+
+/**
+ * Protocol sell fee at `now` (unix seconds), matching `BondingCurve::current_sell_fee_bps`:
+ * the sell tax decays linearly from `sellTaxStartBps` to `sellFeeBps` over
+ * `sellTaxDurationSeconds` after trading starts.
+ */
+export function calculateSellFeeBps(
+  curve: {
+    sellFeeBps: number;
+    sellTaxStartBps: number;
+    sellTaxDurationSeconds: BN;
+    tradingStartTimestamp: BN;
+  },
+  now: number = Math.floor(Date.now() / 1000)
+): number {
+  const duration = curve.sellTaxDurationSeconds;
+  if (duration.lten(0) || curve.sellTaxStartBps <= curve.sellFeeBps) {
+    return curve.sellFeeBps;
+  }
+
+  let elapsed = new BN(now).sub(curve.tradingStartTimestamp);
+  if (elapsed.isNeg()) {
+    elapsed = new BN(0);
+  }
+  if (elapsed.gte(duration)) {
+    return curve.sellFeeBps;
+  }
+
+  const excessBps = new BN(curve.sellTaxStartBps - curve.sellFeeBps);
+  const decayedExcessBps = excessBps.mul(duration.sub(elapsed)).div(duration);
+
+  return curve.sellFeeBps + decayedExcessBps.toNumber();
+}
+
+/**
+ * Volatility surcharge on the protocol fee at `now` (unix seconds), matching
+ * `DynamicFeeConfig::fee_bps` applied to `BondingCurve::decayed_volatility`.
+ */
+export function calculateDynamicFeeBps(
+  config: {
+    decayWindowSeconds: BN;
+    volatilityCoefficientBps: number;
+    maxDynamicFeeBps: number;
+  },
+  curve: {
+    volatilityAccumulator: BN;
+    volatilityLastUpdateTimestamp: BN;
+  },
+  now: number = Math.floor(Date.now() / 1000)
+): number {
+  const window = config.decayWindowSeconds;
+  if (config.maxDynamicFeeBps === 0 || window.lten(0)) {
+    return 0;
+  }
+
+  let elapsed = new BN(now).sub(curve.volatilityLastUpdateTimestamp);
+  if (elapsed.isNeg()) {
+    elapsed = new BN(0);
+  }
+  if (elapsed.gte(window)) {
+    return 0;
+  }
+
+  const volatilityBps = curve.volatilityAccumulator.mul(window.sub(elapsed)).div(window);
+  const feeBps = volatilityBps.mul(new BN(config.volatilityCoefficientBps)).div(new BN(FEE_DENOMINATOR));
+
+  return BN.min(feeBps, new BN(config.maxDynamicFeeBps)).toNumber();
+}
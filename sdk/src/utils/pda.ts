@@ -20,4 +20,25 @@ export function getCurveTokenAccountPDA(bondingCurve: PublicKey, tokenMint: Publ
     [Buffer.from(SEEDS.CURVE_TOKEN_ACCOUNT), bondingCurve.toBuffer(), tokenMint.toBuffer()],
     PROGRAM_ID
   );
-}
\ No newline at end of file
+}
+
+export function getProtocolFeeVaultPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.PROTOCOL_FEE_VAULT)],
+    PROGRAM_ID
+  );
+}
+
+export function getCreatorFeeVaultPDA(bondingCurve: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.CREATOR_FEE_VAULT), bondingCurve.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+export function getPriceCandlesPDA(bondingCurve: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SEEDS.PRICE_CANDLES), bondingCurve.toBuffer()],
+    PROGRAM_ID
+  );
+}
@@ -17,6 +17,7 @@ import {
     createMintToInstruction,
     getAssociatedTokenAddressSync,
     createTransferCheckedWithTransferHookInstruction,
+    createApproveInstruction,
 } from "@solana/spl-token";
 import {
     PublicKey,
@@ -25,6 +26,10 @@ import {
     sendAndConfirmTransaction,
     Keypair,
     LAMPORTS_PER_SOL,
+    Ed25519Program,
+    ComputeBudgetProgram,
+    TransactionInstruction,
+    SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
 import { assert } from "chai";
 
@@ -47,6 +52,8 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
     let globalConfigPda: PublicKey;
     let bondingCurvePda: PublicKey;
     let curveTokenAccountPda: PublicKey;
+    let creatorFeeVaultPda: PublicKey;
+    let protocolFeeVaultPda: PublicKey;
     let extraAccountMetaListPDA: PublicKey;
     let counterPDA: PublicKey;
 
@@ -59,6 +66,300 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
     const VIRTUAL_TOKEN_RESERVES = new anchor.BN(2_000_000_000); // 2000 tokens
     const VIRTUAL_SOL_RESERVES = new anchor.BN(30_000_000_000); // 30 SOL
 
+    // Curves for the later tests use a Token-2022 mint without a transfer hook,
+    // so their transfers need no remaining accounts
+    const PLAIN_SUPPLY = new anchor.BN(1_000_000); // 1 token
+    const PLAIN_VIRTUAL_TOKEN_RESERVES = new anchor.BN(2_000_000); // 2 tokens
+    const PLAIN_VIRTUAL_SOL_RESERVES = new anchor.BN(LAMPORTS_PER_SOL); // 1 SOL
+
+    interface PlainCurve {
+        mint: PublicKey;
+        bondingCurve: PublicKey;
+        curveTokenAccount: PublicKey;
+        creatorFeeVault: PublicKey;
+    }
+
+    const tokenAccountOf = (mintAddress: PublicKey, owner: PublicKey) =>
+        getAssociatedTokenAddressSync(mintAddress, owner, false, TOKEN_2022_PROGRAM_ID);
+
+    const expectError = async (promise: Promise<unknown>, code: string) => {
+        try {
+            await promise;
+        } catch (error: any) {
+            const details = `${error.toString()}\n${(error.logs ?? []).join("\n")}`;
+            assert.include(details, code);
+            return;
+        }
+        assert.fail(`Expected ${code}`);
+    };
+
+    const createPlainCurve = async (launchParams: any = null): Promise<PlainCurve> => {
+        const mintKeypair = Keypair.generate();
+        await createMint(
+            connection,
+            creator,
+            creator.publicKey,
+            null,
+            DECIMALS,
+            mintKeypair,
+            { commitment: "confirmed" },
+            TOKEN_2022_PROGRAM_ID
+        );
+        const creatorAta = await getOrCreateAssociatedTokenAccount(
+            connection,
+            creator,
+            mintKeypair.publicKey,
+            creator.publicKey,
+            false,
+            "confirmed",
+            { commitment: "confirmed" },
+            TOKEN_2022_PROGRAM_ID
+        );
+        await mintTo(
+            connection,
+            creator,
+            mintKeypair.publicKey,
+            creatorAta.address,
+            creator,
+            BigInt(PLAIN_SUPPLY.toString()),
+            [],
+            { commitment: "confirmed" },
+            TOKEN_2022_PROGRAM_ID
+        );
+
+        const [curvePda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("bonding_curve"), mintKeypair.publicKey.toBuffer()],
+            hookAmmProgram.programId
+        );
+        const [curveTokenAccount] = PublicKey.findProgramAddressSync(
+            [Buffer.from("curve_token_account"), mintKeypair.publicKey.toBuffer()],
+            hookAmmProgram.programId
+        );
+        const [creatorFeeVault] = PublicKey.findProgramAddressSync(
+            [Buffer.from("creator_fee_vault"), curvePda.toBuffer()],
+            hookAmmProgram.programId
+        );
+
+        const globalConfig = await hookAmmProgram.account.globalConfig.fetch(globalConfigPda);
+        const [curveByIndexPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("curve_by_index"), globalConfig.totalCurves.toArrayLike(Buffer, "le", 8)],
+            hookAmmProgram.programId
+        );
+        const [creatorCurveCounterPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("creator_curve_counter"), creator.publicKey.toBuffer()],
+            hookAmmProgram.programId
+        );
+        const creatorCurveCounter = await hookAmmProgram.account.creatorCurveCounter.fetchNullable(creatorCurveCounterPda);
+        const curveCount = creatorCurveCounter ? creatorCurveCounter.curveCount : new anchor.BN(0);
+        const [curveByCreatorPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("curve_by_creator"), creator.publicKey.toBuffer(), curveCount.toArrayLike(Buffer, "le", 8)],
+            hookAmmProgram.programId
+        );
+
+        await hookAmmProgram.methods
+            .createBondingCurve({
+                initialSupply: PLAIN_SUPPLY,
+                virtualTokenReserves: PLAIN_VIRTUAL_TOKEN_RESERVES,
+                virtualSolReserves: PLAIN_VIRTUAL_SOL_RESERVES,
+                launchParams,
+                creatorFeeBps: 0,
+                feeTier: 0,
+                sellTaxSchedule: null,
+            })
+            .accounts({
+                bondingCurve: curvePda,
+                curveTokenAccount,
+                creatorFeeVault,
+                creatorTokenAccount: creatorAta.address,
+                mint: mintKeypair.publicKey,
+                creator: creator.publicKey,
+                globalConfig: globalConfigPda,
+                curveByIndex: curveByIndexPda,
+                creatorCurveCounter: creatorCurveCounterPda,
+                curveByCreator: curveByCreatorPda,
+                tokenProgram: TOKEN_2022_PROGRAM_ID,
+                associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            })
+            .signers([creator])
+            .rpc();
+
+        return { mint: mintKeypair.publicKey, bondingCurve: curvePda, curveTokenAccount, creatorFeeVault };
+    };
+
+    // Optional buy accounts, all omitted unless given
+    interface BuyOptions {
+        recipient?: PublicKey;
+        launchPurchase?: PublicKey;
+        referrer?: PublicKey;
+        userPosition?: PublicKey;
+        priceCandles?: PublicKey;
+    }
+
+    const buyOnCurve = (curve: PlainCurve, user: Keypair, solAmount: anchor.BN, options: BuyOptions = {}) =>
+        hookAmmProgram.methods
+            .buy(solAmount, new anchor.BN(0))
+            .accounts({
+                bondingCurve: curve.bondingCurve,
+                curveTokenAccount: curve.curveTokenAccount,
+                userTokenAccount: tokenAccountOf(curve.mint, options.recipient ?? user.publicKey),
+                user: user.publicKey,
+                recipient: options.recipient ?? null,
+                mint: curve.mint,
+                globalConfig: globalConfigPda,
+                protocolFeeVault: protocolFeeVaultPda,
+                creatorFeeVault: curve.creatorFeeVault,
+                launchPurchase: options.launchPurchase ?? null,
+                referrer: options.referrer ?? null,
+                userPosition: options.userPosition ?? null,
+                priceCandles: options.priceCandles ?? null,
+                tokenProgram: TOKEN_2022_PROGRAM_ID,
+                associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            })
+            .signers([user])
+            .rpc();
+
+    // Optional sell accounts, all omitted unless given
+    interface SellOptions {
+        owner?: PublicKey;
+        solRecipient?: PublicKey;
+        referrer?: PublicKey;
+        userPosition?: PublicKey;
+        priceCandles?: PublicKey;
+    }
+
+    const sellAccountsFor = (curve: PlainCurve, user: Keypair, options: SellOptions = {}) => ({
+        bondingCurve: curve.bondingCurve,
+        curveTokenAccount: curve.curveTokenAccount,
+        userTokenAccount: tokenAccountOf(curve.mint, options.owner ?? user.publicKey),
+        user: user.publicKey,
+        owner: options.owner ?? null,
+        solRecipient: options.solRecipient ?? null,
+        mint: curve.mint,
+        globalConfig: globalConfigPda,
+        protocolFeeVault: protocolFeeVaultPda,
+        creatorFeeVault: curve.creatorFeeVault,
+        referrer: options.referrer ?? null,
+        userPosition: options.userPosition ?? null,
+        priceCandles: options.priceCandles ?? null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    });
+
+    const sellOnCurve = (curve: PlainCurve, user: Keypair, tokenAmount: anchor.BN, options: SellOptions = {}) =>
+        hookAmmProgram.methods
+            .sell(tokenAmount, new anchor.BN(0), false)
+            .accounts(sellAccountsFor(curve, user, options))
+            .signers([user])
+            .rpc();
+
+    const sellAllOnCurve = (curve: PlainCurve, user: Keypair, closeTokenAccount: boolean, options: SellOptions = {}) =>
+        hookAmmProgram.methods
+            .sellAll(new anchor.BN(0), closeTokenAccount)
+            .accounts(sellAccountsFor(curve, user, options))
+            .signers([user])
+            .rpc();
+
+    const approveDelegate = (curve: PlainCurve, owner: Keypair, delegate: PublicKey, amount: anchor.BN) =>
+        sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                createApproveInstruction(
+                    tokenAccountOf(curve.mint, owner.publicKey),
+                    delegate,
+                    owner.publicKey,
+                    BigInt(amount.toString()),
+                    [],
+                    TOKEN_2022_PROGRAM_ID
+                )
+            ),
+            [owner],
+            { commitment: "confirmed" }
+        );
+
+    const tokenBalanceOf = async (curve: PlainCurve, owner: PublicKey) =>
+        new anchor.BN((await connection.getTokenAccountBalance(tokenAccountOf(curve.mint, owner))).value.amount);
+
+    const spotPriceOf = async (curve: PlainCurve): Promise<anchor.BN> => {
+        const state = await hookAmmProgram.methods
+            .getCurveState()
+            .accounts({
+                bondingCurve: curve.bondingCurve,
+                curveTokenAccount: curve.curveTokenAccount,
+                mint: curve.mint,
+                globalConfig: globalConfigPda,
+            })
+            .view();
+        return state.spotPrice;
+    };
+
+    const tradeEventsOf = async (signature: string) => {
+        await connection.confirmTransaction(signature, "confirmed");
+        const tx = await connection.getTransaction(signature, {
+            commitment: "confirmed",
+            maxSupportedTransactionVersion: 0,
+        });
+        const parser = new anchor.EventParser(hookAmmProgram.programId, hookAmmProgram.coder);
+        return [...parser.parseLogs(tx.meta.logMessages)]
+            .filter((event) => event.name === "tradeEvent")
+            .map((event) => event.data as any);
+    };
+
+    const fundedKeypair = async () => {
+        const keypair = Keypair.generate();
+        await connection.confirmTransaction(
+            await connection.requestAirdrop(keypair.publicKey, LAMPORTS_PER_SOL),
+            "confirmed"
+        );
+        return keypair;
+    };
+
+    // A fresh curve where `owner` already holds tokens, plus a funded keeper
+    // and the accounts shared by the keeper-executed order instructions
+    const createKeeperFixture = async (owner: Keypair, ownerBuyAmount: anchor.BN) => {
+        const curve = await createPlainCurve();
+        const keeper = await fundedKeypair();
+        await buyOnCurve(curve, owner, ownerBuyAmount);
+
+        const orderPda = (seed: string, orderId: number) =>
+            PublicKey.findProgramAddressSync(
+                [
+                    Buffer.from(seed),
+                    curve.bondingCurve.toBuffer(),
+                    owner.publicKey.toBuffer(),
+                    new anchor.BN(orderId).toArrayLike(Buffer, "le", 8),
+                ],
+                hookAmmProgram.programId
+            )[0];
+
+        const crankAccounts = {
+            owner: owner.publicKey,
+            keeper: keeper.publicKey,
+            bondingCurve: curve.bondingCurve,
+            curveTokenAccount: curve.curveTokenAccount,
+            mint: curve.mint,
+            globalConfig: globalConfigPda,
+            protocolFeeVault: protocolFeeVaultPda,
+            creatorFeeVault: curve.creatorFeeVault,
+            priceCandles: null,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        };
+
+        return {
+            curve,
+            keeper,
+            ownerTokenAccount: tokenAccountOf(curve.mint, owner.publicKey),
+            orderPda,
+            crankAccounts,
+        };
+    };
+
     before(async () => {
         console.log("🚀 Setting up Token-2022 AMM test...");
 
@@ -91,6 +392,11 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
             hookAmmProgram.programId
         );
 
+        [protocolFeeVaultPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("protocol_fee_vault")],
+            hookAmmProgram.programId
+        );
+
         [bondingCurvePda] = PublicKey.findProgramAddressSync(
             [Buffer.from("bonding_curve"), mint.publicKey.toBuffer()],
             hookAmmProgram.programId
@@ -101,6 +407,11 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
             hookAmmProgram.programId
         );
 
+        [creatorFeeVaultPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("creator_fee_vault"), bondingCurvePda.toBuffer()],
+            hookAmmProgram.programId
+        );
+
         [extraAccountMetaListPDA] = PublicKey.findProgramAddressSync(
             [Buffer.from("extra-account-metas"), mint.publicKey.toBuffer()],
             transferHookProgram.programId
@@ -148,6 +459,7 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
             .initializeGlobalConfig()
             .accounts({
                 globalConfig: globalConfigPda,
+                protocolFeeVault: protocolFeeVaultPda,
                 authority: authority.publicKey,
                 feeRecipient: feeRecipient.publicKey,
                 systemProgram: SystemProgram.programId,
@@ -268,19 +580,42 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
         const initialCounter = await transferHookProgram.account.counterAccount.fetch(counterPDA);
         console.log(`📊 Counter before pool creation: ${initialCounter.counter}`);
 
+        // Registry PDAs: by global curve index and by the creator's curve count
+        const globalConfig = await hookAmmProgram.account.globalConfig.fetch(globalConfigPda);
+        const [curveByIndexPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("curve_by_index"), globalConfig.totalCurves.toArrayLike(Buffer, "le", 8)],
+            hookAmmProgram.programId
+        );
+        const [creatorCurveCounterPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("creator_curve_counter"), creator.publicKey.toBuffer()],
+            hookAmmProgram.programId
+        );
+        const [curveByCreatorPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("curve_by_creator"), creator.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+            hookAmmProgram.programId
+        );
+
         const tx = await hookAmmProgram.methods
             .createBondingCurve({
                 initialSupply: INITIAL_SUPPLY,
                 virtualTokenReserves: VIRTUAL_TOKEN_RESERVES,
                 virtualSolReserves: VIRTUAL_SOL_RESERVES,
+                launchParams: null,
+                creatorFeeBps: 0,
+                feeTier: 0,
+                sellTaxSchedule: null,
             })
             .accounts({
                 bondingCurve: bondingCurvePda,
                 curveTokenAccount: curveTokenAccountPda,
+                creatorFeeVault: creatorFeeVaultPda,
                 creatorTokenAccount: creatorTokenAccount,
                 mint: mint.publicKey,
                 creator: creator.publicKey,
                 globalConfig: globalConfigPda,
+                curveByIndex: curveByIndexPda,
+                creatorCurveCounter: creatorCurveCounterPda,
+                curveByCreator: curveByCreatorPda,
                 tokenProgram: TOKEN_2022_PROGRAM_ID,
                 associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -324,6 +659,20 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
         // Verify curve token account balance
         const curveBalance = await connection.getTokenAccountBalance(curveTokenAccountPda);
         console.log(`🏦 Curve token balance: ${curveBalance.value.amount}`);
+
+        // Verify registry entries point at the new curve
+        const curveByIndex = await hookAmmProgram.account.curveRegistryEntry.fetch(curveByIndexPda);
+        const curveByCreator = await hookAmmProgram.account.curveRegistryEntry.fetch(curveByCreatorPda);
+        const creatorCurveCounter = await hookAmmProgram.account.creatorCurveCounter.fetch(creatorCurveCounterPda);
+        console.log(`📇 Registered as curve #${curveByIndex.index.toString()}`);
+
+        for (const entry of [curveByIndex, curveByCreator]) {
+            assert.isTrue(entry.mint.equals(mint.publicKey), "Registry entry should point at the mint");
+            assert.isTrue(entry.bondingCurve.equals(bondingCurvePda), "Registry entry should point at the curve");
+            assert.equal(entry.index.toString(), globalConfig.totalCurves.toString());
+        }
+        assert.isTrue(creatorCurveCounter.creator.equals(creator.publicKey));
+        assert.equal(creatorCurveCounter.curveCount.toNumber(), 1);
     });
 
     it("6. Test buy tokens from pool (SOL → Token with transfer hooks)", async () => {
@@ -341,8 +690,6 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
         console.log(`  Buyer token balance: ${initialBuyerBalance.value.amount}`);
         console.log(`  Buyer SOL balance: ${initialBuyerSol / LAMPORTS_PER_SOL} SOL`);
 
-        const globalConfig = await hookAmmProgram.account.globalConfig.fetch(globalConfigPda);
-
         const tx = await hookAmmProgram.methods
             .buy(solAmount, minTokenAmount)
             .accounts({
@@ -352,7 +699,8 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
                 user: buyer.publicKey,
                 mint: mint.publicKey,
                 globalConfig: globalConfigPda,
-                feeRecipient: globalConfig.feeRecipient,
+                protocolFeeVault: protocolFeeVaultPda,
+                creatorFeeVault: creatorFeeVaultPda,
                 tokenProgram: TOKEN_2022_PROGRAM_ID,
                 associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -429,10 +777,8 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
         console.log(`  Seller token balance: ${initialSellerBalance.value.amount}`);
         console.log(`  Seller SOL balance: ${initialSellerSol / LAMPORTS_PER_SOL} SOL`);
 
-        const globalConfig = await hookAmmProgram.account.globalConfig.fetch(globalConfigPda);
-
         const tx = await hookAmmProgram.methods
-            .sell(tokenAmount, minSolAmount)
+            .sell(tokenAmount, minSolAmount, false)
             .accounts({
                 bondingCurve: bondingCurvePda,
                 curveTokenAccount: curveTokenAccountPda,
@@ -440,7 +786,8 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
                 user: seller.publicKey,
                 mint: mint.publicKey,
                 globalConfig: globalConfigPda,
-                feeRecipient: globalConfig.feeRecipient,
+                protocolFeeVault: protocolFeeVaultPda,
+                creatorFeeVault: creatorFeeVaultPda,
                 tokenProgram: TOKEN_2022_PROGRAM_ID,
                 associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -491,11 +838,9 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
 
         console.log(`❌ Attempting to sell ${largeTokenAmount.toNumber()} tokens (should fail)...`);
 
-        const globalConfig = await hookAmmProgram.account.globalConfig.fetch(globalConfigPda);
-
         try {
             const tx = await hookAmmProgram.methods
-                .sell(largeTokenAmount, minSolAmount)
+                .sell(largeTokenAmount, minSolAmount, false)
                 .accounts({
                     bondingCurve: bondingCurvePda,
                     curveTokenAccount: curveTokenAccountPda,
@@ -503,7 +848,8 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
                     user: seller.publicKey,
                     mint: mint.publicKey,
                     globalConfig: globalConfigPda,
-                    feeRecipient: globalConfig.feeRecipient,
+                    protocolFeeVault: protocolFeeVaultPda,
+                    creatorFeeVault: creatorFeeVaultPda,
                     tokenProgram: TOKEN_2022_PROGRAM_ID,
                     associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
                     systemProgram: SystemProgram.programId,
@@ -543,7 +889,692 @@ describe("Token-2022 AMM with Transfer Hooks", () => {
         }
     });
 
-    it("9. Final state summary", async () => {
+    it("9. Verify curve bookkeeping matches balances", async () => {
+        const verification = await hookAmmProgram.methods
+            .verifyCurve()
+            .accounts({
+                bondingCurve: bondingCurvePda,
+                curveTokenAccount: curveTokenAccountPda,
+                mint: mint.publicKey,
+            })
+            .view();
+
+        console.log(`🔎 Curve verification:`);
+        console.log(`  Lamports: ${verification.actualLamports.toString()} (expected ${verification.expectedLamports.toString()})`);
+        console.log(`  Tokens: ${verification.actualTokenBalance.toString()} (expected ${verification.expectedTokenBalance.toString()})`);
+
+        assert.isTrue(verification.isSolvent, "Curve should be solvent");
+        assert.isTrue(verification.isConsistent, "Curve bookkeeping should match balances");
+    });
+
+    it("10. Partially fill a buy larger than the remaining supply", async () => {
+        const curve = await createPlainCurve();
+        const solAmount = new anchor.BN(LAMPORTS_PER_SOL); // the whole supply costs about 0.5 SOL
+        const buyerSolBefore = await connection.getBalance(buyer.publicKey);
+
+        await buyOnCurve(curve, buyer, solAmount);
+
+        const buyerBalance = await connection.getTokenAccountBalance(tokenAccountOf(curve.mint, buyer.publicKey));
+        const curveBalance = await connection.getTokenAccountBalance(curve.curveTokenAccount);
+        const solSpent = buyerSolBefore - await connection.getBalance(buyer.publicKey);
+        console.log(`🧮 Partial fill: ${buyerBalance.value.amount} tokens for ${solSpent / LAMPORTS_PER_SOL} SOL`);
+
+        assert.equal(buyerBalance.value.amount, PLAIN_SUPPLY.toString(), "Buyer should receive the remaining supply");
+        assert.equal(curveBalance.value.amount, "0", "Curve should be sold out");
+        assert.isBelow(solSpent, solAmount.toNumber() * 0.6, "Unspent SOL should be refunded");
+    });
+
+    it("11. Reject buys over the per-wallet launch cap", async () => {
+        const curve = await createPlainCurve({
+            tradingStartTimestamp: new anchor.BN(0), // start immediately
+            protectedWindowSeconds: new anchor.BN(3600),
+            maxTokensPerWallet: new anchor.BN(5_000),
+        });
+        const [launchPurchasePda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("launch_purchase"), curve.bondingCurve.toBuffer(), buyer.publicKey.toBuffer()],
+            hookAmmProgram.programId
+        );
+        const smallBuy = new anchor.BN(1_000_000); // about 3,000 token units
+
+        await expectError(buyOnCurve(curve, buyer, smallBuy), "LaunchPurchaseRequired");
+        await buyOnCurve(curve, buyer, smallBuy, { launchPurchase: launchPurchasePda });
+        await expectError(buyOnCurve(curve, buyer, smallBuy, { launchPurchase: launchPurchasePda }), "WalletBuyCapExceeded");
+
+        const launchPurchase = await hookAmmProgram.account.launchPurchase.fetch(launchPurchasePda);
+        console.log(`🛡️ Launch purchases: ${launchPurchase.tokensPurchased.toString()} of 5000 token units`);
+        assert.isTrue(launchPurchase.tokensPurchased.lten(5_000), "Cap should hold");
+    });
+
+    it("12. Reject trades over the price impact limit", async () => {
+        const curve = await createPlainCurve();
+        const setMaxPriceImpactBps = (maxPriceImpactBps: number) =>
+            hookAmmProgram.methods
+                .updateGlobalConfig({
+                    newAuthority: null,
+                    feeRecipient: null,
+                    minCreatorFeeBps: null,
+                    maxCreatorFeeBps: null,
+                    referralShareBps: null,
+                    feeTiers: null,
+                    dynamicFee: null,
+                    maxPriceImpactBps,
+                    maxSlotPriceMoveBps: null,
+                })
+                .accounts({
+                    globalConfig: globalConfigPda,
+                    authority: authority.publicKey,
+                })
+                .signers([authority])
+                .rpc();
+
+        await setMaxPriceImpactBps(100); // 1%
+        try {
+            // 0.001 SOL moves the price about 0.2%, 0.1 SOL about 20%
+            await buyOnCurve(curve, buyer, new anchor.BN(1_000_000));
+            await expectError(buyOnCurve(curve, buyer, new anchor.BN(100_000_000)), "PriceImpactTooHigh");
+            console.log("✅ High-impact trade rejected");
+        } finally {
+            await setMaxPriceImpactBps(0);
+        }
+    });
+
+    it("13. Place, execute and cancel limit orders", async () => {
+        const owner = seller;
+        const { curve, keeper, ownerTokenAccount, orderPda, crankAccounts } =
+            await createKeeperFixture(owner, new anchor.BN(10_000_000)); // tokens for the sell orders
+
+        const keeperReward = new anchor.BN(1_000_000);
+        const expiryTimestamp = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+        const orderAccounts = (orderId: number, isBuy: boolean) => {
+            const limitOrder = orderPda("limit_order", orderId);
+            const [escrow] = PublicKey.findProgramAddressSync(
+                [Buffer.from("limit_order_escrow"), limitOrder.toBuffer()],
+                hookAmmProgram.programId
+            );
+            return { limitOrder, orderTokenAccount: isBuy ? null : escrow, escrow };
+        };
+
+        const placeOrder = (orderId: number, isBuy: boolean, amountIn: anchor.BN, targetPrice: anchor.BN) => {
+            const { limitOrder, orderTokenAccount } = orderAccounts(orderId, isBuy);
+            return hookAmmProgram.methods
+                .placeLimitOrder({
+                    orderId: new anchor.BN(orderId),
+                    isBuy,
+                    amountIn,
+                    targetPrice,
+                    minAmountOut: new anchor.BN(0),
+                    expiryTimestamp,
+                    keeperReward,
+                })
+                .accounts({
+                    limitOrder,
+                    orderTokenAccount,
+                    ownerTokenAccount: isBuy ? null : ownerTokenAccount,
+                    bondingCurve: curve.bondingCurve,
+                    mint: curve.mint,
+                    owner: owner.publicKey,
+                    tokenProgram: TOKEN_2022_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([owner])
+                .rpc();
+        };
+
+        const executeOrder = (orderId: number, isBuy: boolean) => {
+            const { limitOrder, orderTokenAccount } = orderAccounts(orderId, isBuy);
+            return hookAmmProgram.methods
+                .executeOrder()
+                .accounts({
+                    limitOrder,
+                    orderTokenAccount,
+                    ownerTokenAccount: isBuy ? ownerTokenAccount : null,
+                    ...crankAccounts,
+                })
+                .signers([keeper])
+                .rpc();
+        };
+
+        const cancelOrder = (orderId: number, isBuy: boolean) => {
+            const { limitOrder, orderTokenAccount } = orderAccounts(orderId, isBuy);
+            return hookAmmProgram.methods
+                .cancelLimitOrder()
+                .accounts({
+                    limitOrder,
+                    orderTokenAccount,
+                    ownerTokenAccount: isBuy ? null : ownerTokenAccount,
+                    bondingCurve: curve.bondingCurve,
+                    mint: curve.mint,
+                    owner: owner.publicKey,
+                    tokenProgram: TOKEN_2022_PROGRAM_ID,
+                })
+                .signers([owner])
+                .rpc();
+        };
+
+        const tokenBalance = async (account: PublicKey) =>
+            new anchor.BN((await connection.getTokenAccountBalance(account)).value.amount);
+
+        // Buy order below the spot price: SOL is escrowed and the order waits
+        const buyAmount = new anchor.BN(10_000_000);
+        await placeOrder(0, true, buyAmount, (await spotPriceOf(curve)).divn(2));
+        const restingBuy = orderAccounts(0, true).limitOrder;
+        const restingBuyInfo = await connection.getAccountInfo(restingBuy);
+        const restingBuyRent = await connection.getMinimumBalanceForRentExemption(restingBuyInfo.data.length);
+        assert.equal(restingBuyInfo.lamports - restingBuyRent, buyAmount.add(keeperReward).toNumber(), "Order should escrow the SOL and keeper reward");
+
+        await expectError(executeOrder(0, true), "OrderNotTriggered");
+
+        const ownerSolBeforeCancel = await connection.getBalance(owner.publicKey);
+        await cancelOrder(0, true);
+        assert.equal(await connection.getBalance(owner.publicKey) - ownerSolBeforeCancel, restingBuyInfo.lamports, "Cancel should refund the escrow");
+        assert.isNull(await connection.getAccountInfo(restingBuy), "Cancelled order should be closed");
+
+        // Buy order above the spot price: the keeper fills it and is paid
+        await placeOrder(1, true, buyAmount, (await spotPriceOf(curve)).muln(2));
+        const filledBuy = orderAccounts(1, true).limitOrder;
+        const ownerTokensBeforeBuy = await tokenBalance(ownerTokenAccount);
+        const ownerSolBeforeBuy = await connection.getBalance(owner.publicKey);
+        const keeperSolBeforeBuy = await connection.getBalance(keeper.publicKey);
+
+        await executeOrder(1, true);
+
+        assert.equal(await connection.getBalance(keeper.publicKey) - keeperSolBeforeBuy, keeperReward.toNumber(), "Keeper should be paid");
+        assert.isTrue((await tokenBalance(ownerTokenAccount)).gt(ownerTokensBeforeBuy), "Owner should receive tokens");
+        assert.isAbove(await connection.getBalance(owner.publicKey), ownerSolBeforeBuy, "Order rent should return to the owner");
+        assert.isNull(await connection.getAccountInfo(filledBuy), "Filled order should be closed");
+
+        // Sell order above the spot price: tokens are escrowed until cancelled
+        const sellAmount = new anchor.BN(1_000);
+        const ownerTokensBeforeSell = await tokenBalance(ownerTokenAccount);
+        await placeOrder(2, false, sellAmount, (await spotPriceOf(curve)).muln(1_000));
+        const restingSell = orderAccounts(2, false);
+        assert.isTrue((await tokenBalance(restingSell.escrow)).eq(sellAmount), "Order should escrow the tokens");
+        assert.isTrue((await tokenBalance(ownerTokenAccount)).eq(ownerTokensBeforeSell.sub(sellAmount)));
+
+        await expectError(executeOrder(2, false), "OrderNotTriggered");
+
+        await cancelOrder(2, false);
+        assert.isTrue((await tokenBalance(ownerTokenAccount)).eq(ownerTokensBeforeSell), "Cancel should refund the tokens");
+        assert.isNull(await connection.getAccountInfo(restingSell.escrow), "Escrow should be closed");
+        assert.isNull(await connection.getAccountInfo(restingSell.limitOrder), "Cancelled order should be closed");
+
+        // Sell order below the spot price: the keeper fills it and the owner gets SOL
+        await placeOrder(3, false, sellAmount, new anchor.BN(1));
+        const filledSell = orderAccounts(3, false);
+        const ownerSolBeforeSell = await connection.getBalance(owner.publicKey);
+        const keeperSolBeforeSell = await connection.getBalance(keeper.publicKey);
+
+        await executeOrder(3, false);
+
+        assert.equal(await connection.getBalance(keeper.publicKey) - keeperSolBeforeSell, keeperReward.toNumber(), "Keeper should be paid");
+        assert.isAbove(await connection.getBalance(owner.publicKey), ownerSolBeforeSell, "Owner should receive the proceeds");
+        assert.isNull(await connection.getAccountInfo(filledSell.escrow), "Escrow should be closed");
+        assert.isNull(await connection.getAccountInfo(filledSell.limitOrder), "Filled order should be closed");
+        console.log("✅ Limit orders escrowed, executed and cancelled");
+    });
+
+    it("14. Fill DCA orders on schedule", async () => {
+        const owner = seller;
+        const { curve, keeper, ownerTokenAccount, orderPda, crankAccounts } =
+            await createKeeperFixture(owner, new anchor.BN(1_000_000)); // creates the owner's token account
+
+        const dcaOrderPda = (orderId: number) => orderPda("dca_order", orderId);
+
+        const createOrder = (orderId: number, fillCount: number, maxPrice: anchor.BN) =>
+            hookAmmProgram.methods
+                .createDcaOrder({
+                    orderId: new anchor.BN(orderId),
+                    amountPerFill: new anchor.BN(1_000_000),
+                    fillCount: new anchor.BN(fillCount),
+                    intervalSeconds: new anchor.BN(3600),
+                    maxPrice,
+                    keeperReward: new anchor.BN(1_000_000),
+                })
+                .accounts({
+                    dcaOrder: dcaOrderPda(orderId),
+                    bondingCurve: curve.bondingCurve,
+                    mint: curve.mint,
+                    owner: owner.publicKey,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([owner])
+                .rpc();
+
+        const executeFill = (orderId: number) =>
+            hookAmmProgram.methods
+                .executeDcaFill()
+                .accounts({
+                    dcaOrder: dcaOrderPda(orderId),
+                    ownerTokenAccount,
+                    ...crankAccounts,
+                })
+                .signers([keeper])
+                .rpc();
+
+        // A fill must carry a price bound
+        await expectError(createOrder(0, 2, new anchor.BN(0)), "InvalidAmount");
+
+        // Only one fill per interval
+        const maxPrice = (await spotPriceOf(curve)).muln(2);
+        await createOrder(0, 2, maxPrice);
+        await executeFill(0);
+        await expectError(executeFill(0), "DcaFillNotDue");
+        const pendingOrder = await hookAmmProgram.account.dcaOrder.fetch(dcaOrderPda(0));
+        assert.equal(pendingOrder.fillsRemaining.toNumber(), 1);
+
+        // A fill whose average price exceeds max_price is rejected
+        await createOrder(1, 1, (await spotPriceOf(curve)).divn(2));
+        await expectError(executeFill(1), "SlippageExceeded");
+
+        // The last fill closes the order to its owner
+        await createOrder(2, 1, maxPrice);
+        const orderLamports = await connection.getBalance(dcaOrderPda(2));
+        const ownerSolBefore = await connection.getBalance(owner.publicKey);
+        await executeFill(2);
+        assert.isNull(await connection.getAccountInfo(dcaOrderPda(2)), "Completed order should be closed");
+        assert.isAbove(await connection.getBalance(owner.publicKey), ownerSolBefore, "Order rent should return to the owner");
+        assert.isBelow(await connection.getBalance(owner.publicKey) - ownerSolBefore, orderLamports);
+        console.log("✅ DCA fills respected the interval, max price and close-out");
+    });
+
+    it("15. Execute ed25519-signed sell intents", async () => {
+        const curve = await createPlainCurve();
+        const [user, relayer, otherRelayer] = await Promise.all([fundedKeypair(), fundedKeypair(), fundedKeypair()]);
+        const userTokenAccount = tokenAccountOf(curve.mint, user.publicKey);
+        await buyOnCurve(curve, user, new anchor.BN(10_000_000));
+
+        const [intentAuthorityPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("intent_authority")],
+            hookAmmProgram.programId
+        );
+        const [intentNoncePda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("intent_nonce"), user.publicKey.toBuffer()],
+            hookAmmProgram.programId
+        );
+
+        // One-time approval of the program's intent authority as delegate
+        await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                createApproveInstruction(
+                    userTokenAccount,
+                    intentAuthorityPda,
+                    user.publicKey,
+                    BigInt(1_000_000),
+                    [],
+                    TOKEN_2022_PROGRAM_ID
+                )
+            ),
+            [user],
+            { commitment: "confirmed" }
+        );
+
+        // Domain prefix followed by the Borsh encoding of SellIntent
+        const encodeIntent = (intent: any) =>
+            Buffer.concat([
+                Buffer.from("hook-amm:sell_intent:v1"),
+                intent.bondingCurve.toBuffer(),
+                intent.tokenAmount.toArrayLike(Buffer, "le", 8),
+                intent.minSolAmount.toArrayLike(Buffer, "le", 8),
+                intent.relayerFee.toArrayLike(Buffer, "le", 8),
+                intent.deadline.toTwos(64).toArrayLike(Buffer, "le", 8),
+                intent.nonce.toArrayLike(Buffer, "le", 8),
+            ]);
+
+        const signIntent = (intent: any, signer: Keypair = user) =>
+            Ed25519Program.createInstructionWithPrivateKey({
+                privateKey: signer.secretKey,
+                message: encodeIntent(intent),
+            });
+
+        const sellWithIntent = (
+            intent: any,
+            preInstructions: TransactionInstruction[],
+            relayerSigner: Keypair = relayer
+        ) =>
+            hookAmmProgram.methods
+                .sellWithIntent(intent)
+                .accounts({
+                    bondingCurve: curve.bondingCurve,
+                    curveTokenAccount: curve.curveTokenAccount,
+                    userTokenAccount,
+                    user: user.publicKey,
+                    intentAuthority: intentAuthorityPda,
+                    intentNonce: intentNoncePda,
+                    relayer: relayerSigner.publicKey,
+                    mint: curve.mint,
+                    globalConfig: globalConfigPda,
+                    protocolFeeVault: protocolFeeVaultPda,
+                    creatorFeeVault: curve.creatorFeeVault,
+                    priceCandles: null,
+                    instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+                    tokenProgram: TOKEN_2022_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                })
+                .preInstructions(preInstructions)
+                .signers([relayerSigner])
+                .rpc();
+
+        const chainTime = await connection.getBlockTime(await connection.getSlot());
+        const intent = {
+            bondingCurve: curve.bondingCurve,
+            tokenAmount: new anchor.BN(1_000),
+            minSolAmount: new anchor.BN(100_000),
+            relayerFee: new anchor.BN(10_000),
+            deadline: new anchor.BN(chainTime + 600),
+            nonce: new anchor.BN(0),
+        };
+        const signedIntent = signIntent(intent);
+
+        // Signed by someone other than the token owner
+        await expectError(sellWithIntent(intent, [signIntent(intent, Keypair.generate())]), "InvalidIntentSignature");
+
+        // Terms changed after signing
+        await expectError(
+            sellWithIntent({ ...intent, relayerFee: new anchor.BN(100_000) }, [signedIntent]),
+            "InvalidIntentSignature"
+        );
+        await expectError(
+            sellWithIntent({ ...intent, minSolAmount: new anchor.BN(0) }, [signedIntent]),
+            "InvalidIntentSignature"
+        );
+
+        // Verification must be the instruction immediately before
+        await expectError(
+            sellWithIntent(intent, [signedIntent, ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })]),
+            "InvalidIntentSignature"
+        );
+
+        // Past its deadline
+        const expiredIntent = { ...intent, deadline: new anchor.BN(chainTime - 60) };
+        await expectError(sellWithIntent(expiredIntent, [signIntent(expiredIntent)]), "IntentExpired");
+
+        // Valid intent
+        const userTokensBefore = new anchor.BN((await connection.getTokenAccountBalance(userTokenAccount)).value.amount);
+        const userSolBefore = await connection.getBalance(user.publicKey);
+        const relayerSolBefore = await connection.getBalance(relayer.publicKey);
+
+        await sellWithIntent(intent, [signedIntent]);
+
+        const userTokensAfter = new anchor.BN((await connection.getTokenAccountBalance(userTokenAccount)).value.amount);
+        const nonceRent = await connection.getBalance(intentNoncePda);
+        const intentNonce = await hookAmmProgram.account.intentNonce.fetch(intentNoncePda);
+        assert.isTrue(userTokensBefore.sub(userTokensAfter).eq(intent.tokenAmount), "Intent tokens should be sold");
+        assert.isAtLeast(await connection.getBalance(user.publicKey) - userSolBefore, intent.minSolAmount.toNumber());
+        assert.equal(
+            await connection.getBalance(relayer.publicKey) - relayerSolBefore,
+            intent.relayerFee.toNumber() - nonceRent,
+            "Relayer should earn the fee less the nonce account rent"
+        );
+        assert.equal(intentNonce.nonce.toNumber(), 1);
+
+        // Replaying the same signed intent
+        await expectError(sellWithIntent(intent, [signedIntent], otherRelayer), "InvalidIntentNonce");
+        console.log("✅ Sell intents verified, executed once and rejected when tampered or replayed");
+    });
+
+    it("16. Require price candles once a curve has them", async () => {
+        const curve = await createPlainCurve();
+        const user = await fundedKeypair();
+        const buyAmount = new anchor.BN(0.01 * LAMPORTS_PER_SOL);
+        const [priceCandlesPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("price_candles"), curve.bondingCurve.toBuffer()],
+            hookAmmProgram.programId
+        );
+
+        await buyOnCurve(curve, user, buyAmount);
+
+        await hookAmmProgram.methods
+            .initializePriceCandles()
+            .accounts({
+                priceCandles: priceCandlesPda,
+                bondingCurve: curve.bondingCurve,
+                payer: user.publicKey,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([user])
+            .rpc();
+        assert.isTrue((await hookAmmProgram.account.bondingCurve.fetch(curve.bondingCurve)).priceCandlesInitialized);
+
+        await expectError(buyOnCurve(curve, user, buyAmount), "PriceCandlesRequired");
+
+        await buyOnCurve(curve, user, buyAmount, { priceCandles: priceCandlesPda });
+        const priceCandles = await hookAmmProgram.account.priceCandles.fetch(priceCandlesPda);
+        const minuteCandle = priceCandles.minuteCandles[priceCandles.minuteHead];
+        assert.equal(minuteCandle.tradeCount.toNumber(), 1);
+        assert.isTrue(minuteCandle.solVolume.eq(buyAmount));
+    });
+
+    it("17. Buy tokens for a recipient under the launch cap", async () => {
+        const curve = await createPlainCurve({
+            tradingStartTimestamp: new anchor.BN(0), // start immediately
+            protectedWindowSeconds: new anchor.BN(3600),
+            maxTokensPerWallet: new anchor.BN(5_000),
+        });
+        const [payer, otherPayer] = await Promise.all([fundedKeypair(), fundedKeypair()]);
+        const recipient = Keypair.generate().publicKey;
+        const launchPurchaseOf = (owner: PublicKey) =>
+            PublicKey.findProgramAddressSync(
+                [Buffer.from("launch_purchase"), curve.bondingCurve.toBuffer(), owner.toBuffer()],
+                hookAmmProgram.programId
+            )[0];
+        const smallBuy = new anchor.BN(1_000_000); // about 3,000 token units
+
+        // The allocation belongs to the token owner, not the payer
+        await expectError(
+            buyOnCurve(curve, payer, smallBuy, { recipient, launchPurchase: launchPurchaseOf(payer.publicKey) }),
+            "ConstraintSeeds"
+        );
+        const signature = await buyOnCurve(curve, payer, smallBuy, {
+            recipient,
+            launchPurchase: launchPurchaseOf(recipient),
+        });
+
+        const recipientBalance = await connection.getTokenAccountBalance(tokenAccountOf(curve.mint, recipient));
+        assert.isAbove(Number(recipientBalance.value.amount), 0, "Recipient should receive the tokens");
+        assert.isNull(await connection.getAccountInfo(tokenAccountOf(curve.mint, payer.publicKey)));
+
+        const launchPurchase = await hookAmmProgram.account.launchPurchase.fetch(launchPurchaseOf(recipient));
+        assert.isTrue(launchPurchase.user.equals(recipient));
+        assert.equal(launchPurchase.tokensPurchased.toString(), recipientBalance.value.amount);
+
+        const [tradeEvent] = await tradeEventsOf(signature);
+        assert.isTrue(tradeEvent.user.equals(payer.publicKey));
+        assert.isTrue(tradeEvent.recipient.equals(recipient));
+        assert.equal(tradeEvent.tokenAmount.toString(), recipientBalance.value.amount);
+
+        // Another payer cannot top the same owner past the cap
+        await expectError(
+            buyOnCurve(curve, otherPayer, smallBuy, { recipient, launchPurchase: launchPurchaseOf(recipient) }),
+            "WalletBuyCapExceeded"
+        );
+        console.log(`🎁 Bought ${recipientBalance.value.amount} token units for a recipient`);
+    });
+
+    it("18. Accrue and claim referral fees", async () => {
+        const curve = await createPlainCurve();
+        const [frontend, trader] = await Promise.all([fundedKeypair(), fundedKeypair()]);
+        const [referrerPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("referrer"), frontend.publicKey.toBuffer()],
+            hookAmmProgram.programId
+        );
+
+        await hookAmmProgram.methods
+            .registerReferrer()
+            .accounts({
+                referrer: referrerPda,
+                authority: frontend.publicKey,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([frontend])
+            .rpc();
+
+        // A frontend cannot refer its own trades
+        await expectError(
+            buyOnCurve(curve, frontend, new anchor.BN(1_000_000), { referrer: referrerPda }),
+            "SelfReferral"
+        );
+
+        const referrerLamportsBefore = await connection.getBalance(referrerPda);
+        const [buyEvent] = await tradeEventsOf(
+            await buyOnCurve(curve, trader, new anchor.BN(10_000_000), { referrer: referrerPda })
+        );
+        const [sellEvent] = await tradeEventsOf(
+            await sellOnCurve(curve, trader, buyEvent.tokenAmount.divn(2), { referrer: referrerPda })
+        );
+        assert.isTrue(buyEvent.referralFee.gtn(0), "Buy should pay a referral fee");
+        assert.isTrue(sellEvent.referralFee.gtn(0), "Sell should pay a referral fee");
+
+        const referrer = await hookAmmProgram.account.referrer.fetch(referrerPda);
+        const accrued = buyEvent.referralFee.add(sellEvent.referralFee);
+        assert.equal(referrer.totalAccrued.toString(), accrued.toString());
+        assert.equal(referrer.tradeCount.toNumber(), 2);
+        assert.equal(
+            await connection.getBalance(referrerPda) - referrerLamportsBefore,
+            accrued.toNumber(),
+            "Referral fees should be held by the referrer account"
+        );
+
+        await hookAmmProgram.methods
+            .claimReferralFees()
+            .accounts({ referrer: referrerPda, authority: frontend.publicKey })
+            .signers([frontend])
+            .rpc();
+        const claimed = await hookAmmProgram.account.referrer.fetch(referrerPda);
+        assert.equal(claimed.totalClaimed.toString(), accrued.toString());
+        assert.equal(await connection.getBalance(referrerPda), referrerLamportsBefore);
+
+        await expectError(
+            hookAmmProgram.methods
+                .claimReferralFees()
+                .accounts({ referrer: referrerPda, authority: frontend.publicKey })
+                .signers([frontend])
+                .rpc(),
+            "NothingToClaim"
+        );
+        console.log(`🤝 Referrer claimed ${accrued.toString()} lamports over ${referrer.tradeCount.toString()} trades`);
+    });
+
+    it("19. Quotes match the fills they price", async () => {
+        const curve = await createPlainCurve();
+        const trader = await fundedKeypair();
+        const quoteAccounts = {
+            bondingCurve: curve.bondingCurve,
+            curveTokenAccount: curve.curveTokenAccount,
+            mint: curve.mint,
+            globalConfig: globalConfigPda,
+        };
+        const assertMatchesFill = (quote: any, fill: any) => {
+            assert.equal(fill.tokenAmount.toString(), quote.tokenAmount.toString());
+            assert.equal(fill.creatorFee.toString(), quote.fees.creatorFee.toString());
+            assert.equal(fill.virtualSolReserves.toString(), quote.virtualSolReserves.toString());
+            assert.equal(fill.virtualTokenReserves.toString(), quote.virtualTokenReserves.toString());
+        };
+
+        // Buy side
+        const solAmount = new anchor.BN(10_000_000);
+        const buyQuote = await hookAmmProgram.methods.quoteBuy(solAmount).accounts(quoteAccounts).view();
+        const [buyFill] = await tradeEventsOf(await buyOnCurve(curve, trader, solAmount));
+        assertMatchesFill(buyQuote, buyFill);
+        assert.equal(buyFill.solAmount.toString(), buyQuote.solAmount.toString());
+        assert.equal(buyFill.refundedSolAmount.toString(), buyQuote.refundedSolAmount.toString());
+        const tokenBalance = await connection.getTokenAccountBalance(tokenAccountOf(curve.mint, trader.publicKey));
+        assert.equal(tokenBalance.value.amount, buyQuote.tokenAmount.toString());
+
+        // Sell side
+        const tokenAmount = buyQuote.tokenAmount.divn(2);
+        const sellQuote = await hookAmmProgram.methods.quoteSell(tokenAmount).accounts(quoteAccounts).view();
+        const solBefore = await connection.getBalance(trader.publicKey);
+        const [sellFill] = await tradeEventsOf(await sellOnCurve(curve, trader, tokenAmount));
+        assertMatchesFill(sellQuote, sellFill);
+        assert.equal(sellFill.solAmount.toString(), sellQuote.solAmountAfterFee.toString());
+        assert.isAbove(await connection.getBalance(trader.publicKey), solBefore, "Seller should receive the proceeds");
+        console.log(`🧮 Quotes matched a ${buyQuote.tokenAmount.toString()} token buy and ${tokenAmount.toString()} token sell`);
+    });
+
+    it("20. Sell a whole balance with sell_all", async () => {
+        const curve = await createPlainCurve();
+        const [owner, delegatingOwner, delegate] = await Promise.all([
+            fundedKeypair(),
+            fundedKeypair(),
+            fundedKeypair(),
+        ]);
+        await buyOnCurve(curve, owner, new anchor.BN(10_000_000));
+        await buyOnCurve(curve, delegatingOwner, new anchor.BN(10_000_000));
+
+        // The owner sells everything and reclaims the token account rent
+        const balance = await tokenBalanceOf(curve, owner.publicKey);
+        const [ownerFill] = await tradeEventsOf(await sellAllOnCurve(curve, owner, true));
+        assert.equal(ownerFill.tokenAmount.toString(), balance.toString());
+        assert.isNull(
+            await connection.getAccountInfo(tokenAccountOf(curve.mint, owner.publicKey)),
+            "Emptied token account should be closed"
+        );
+
+        // A delegate's sell_all stops at its allowance
+        const delegatingBalance = await tokenBalanceOf(curve, delegatingOwner.publicKey);
+        const allowance = delegatingBalance.divn(3);
+        await approveDelegate(curve, delegatingOwner, delegate.publicKey, allowance);
+        const [delegateFill] = await tradeEventsOf(
+            await sellAllOnCurve(curve, delegate, false, { owner: delegatingOwner.publicKey })
+        );
+        assert.equal(delegateFill.tokenAmount.toString(), allowance.toString());
+        assert.equal(
+            (await tokenBalanceOf(curve, delegatingOwner.publicKey)).toString(),
+            delegatingBalance.sub(allowance).toString()
+        );
+        console.log(`🧹 sell_all sold ${balance.toString()} owned and ${allowance.toString()} delegated token units`);
+    });
+
+    it("21. Sell as an approved delegate", async () => {
+        const curve = await createPlainCurve();
+        const [owner, delegate, stranger] = await Promise.all([fundedKeypair(), fundedKeypair(), fundedKeypair()]);
+        await buyOnCurve(curve, owner, new anchor.BN(10_000_000));
+        const allowance = (await tokenBalanceOf(curve, owner.publicKey)).divn(2);
+        await approveDelegate(curve, owner, delegate.publicKey, allowance);
+        const asDelegate = { owner: owner.publicKey };
+        const [delegatePositionPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("user_position"), curve.bondingCurve.toBuffer(), delegate.publicKey.toBuffer()],
+            hookAmmProgram.programId
+        );
+
+        // Only the approved delegate, within its allowance, with proceeds to the owner
+        await expectError(sellOnCurve(curve, stranger, allowance, asDelegate), "Unauthorized");
+        await expectError(sellOnCurve(curve, delegate, allowance.addn(1), asDelegate), "Unauthorized");
+        await expectError(
+            sellOnCurve(curve, delegate, allowance, { ...asDelegate, solRecipient: delegate.publicKey }),
+            "Unauthorized"
+        );
+        await expectError(
+            sellOnCurve(curve, delegate, allowance, { ...asDelegate, userPosition: delegatePositionPda }),
+            "Unauthorized"
+        );
+        await expectError(
+            hookAmmProgram.methods
+                .sell(allowance, new anchor.BN(0), true)
+                .accounts(sellAccountsFor(curve, delegate, asDelegate))
+                .signers([delegate])
+                .rpc(),
+            "TokenAccountNotClosable"
+        );
+
+        const ownerSolBefore = await connection.getBalance(owner.publicKey);
+        const [fill] = await tradeEventsOf(await sellOnCurve(curve, delegate, allowance, asDelegate));
+        assert.isTrue(fill.user.equals(owner.publicKey));
+        assert.isTrue(fill.recipient.equals(owner.publicKey));
+        assert.equal(
+            await connection.getBalance(owner.publicKey) - ownerSolBefore,
+            fill.solAmount.toNumber(),
+            "Proceeds should go to the owner"
+        );
+
+        // The allowance is used up
+        await expectError(sellOnCurve(curve, delegate, new anchor.BN(1), asDelegate), "Unauthorized");
+        console.log(`🤖 Delegate sold ${allowance.toString()} token units for the owner`);
+    });
+
+    it("22. Final state summary", async () => {
         console.log("\n🏁 Final State Summary:");
         console.log("=" .repeat(50));
 